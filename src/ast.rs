@@ -0,0 +1,565 @@
+use std::fmt;
+
+use crate::{Keyword, Span};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ident {
+    pub name: String,
+    pub span: Span,
+}
+
+pub const MAX_STRING_LENGTH: u8 = 255;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TypeCategory {
+    Ordinal,
+    Real,
+    Other,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SimpleType {
+    Byte,
+    ShortInt,
+    Word,
+    Integer,
+    LongInt,
+    Boolean,
+    Char,
+    Real,
+    Single,
+    Double,
+    Extended,
+    Comp,
+    Pointer,
+    Text,
+}
+
+const SIMPLE_TYPES: [(SimpleType, Keyword, TypeCategory); 14] = [
+    (SimpleType::Byte, Keyword::Byte, TypeCategory::Ordinal),
+    (
+        SimpleType::ShortInt,
+        Keyword::ShortInt,
+        TypeCategory::Ordinal,
+    ),
+    (SimpleType::Word, Keyword::Word, TypeCategory::Ordinal),
+    (SimpleType::Integer, Keyword::Integer, TypeCategory::Ordinal),
+    (SimpleType::LongInt, Keyword::LongInt, TypeCategory::Ordinal),
+    (SimpleType::Boolean, Keyword::Boolean, TypeCategory::Ordinal),
+    (SimpleType::Char, Keyword::Char, TypeCategory::Ordinal),
+    (SimpleType::Real, Keyword::Real, TypeCategory::Real),
+    (SimpleType::Single, Keyword::Single, TypeCategory::Real),
+    (SimpleType::Double, Keyword::Double, TypeCategory::Real),
+    (SimpleType::Extended, Keyword::Extended, TypeCategory::Real),
+    (SimpleType::Comp, Keyword::Comp, TypeCategory::Real),
+    (SimpleType::Pointer, Keyword::Pointer, TypeCategory::Other),
+    (SimpleType::Text, Keyword::Text, TypeCategory::Other),
+];
+
+impl SimpleType {
+    pub fn from_keyword(keyword: Keyword) -> Option<Self> {
+        SIMPLE_TYPES
+            .iter()
+            .find(|(_, kw, _)| *kw == keyword)
+            .map(|(simple, _, _)| *simple)
+    }
+
+    fn entry(&self) -> &'static (SimpleType, Keyword, TypeCategory) {
+        SIMPLE_TYPES
+            .iter()
+            .find(|(simple, _, _)| simple == self)
+            .unwrap()
+    }
+
+    pub fn category(&self) -> TypeCategory {
+        self.entry().2
+    }
+
+    pub fn is_ordinal(&self) -> bool {
+        self.category() == TypeCategory::Ordinal
+    }
+
+    pub fn is_real(&self) -> bool {
+        self.category() == TypeCategory::Real
+    }
+
+    pub fn bounds(&self) -> Option<(i32, i32)> {
+        match self {
+            SimpleType::Byte | SimpleType::Char => Some((0, 255)),
+            SimpleType::ShortInt => Some((-128, 127)),
+            SimpleType::Word => Some((0, 65535)),
+            SimpleType::Integer => Some((-32768, 32767)),
+            SimpleType::LongInt => Some((i32::MIN, i32::MAX)),
+            SimpleType::Boolean => Some((0, 1)),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        self.entry().1.as_str()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Range {
+    pub low: i32,
+    pub high: i32,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TypeExpr {
+    Simple(SimpleType),
+    Named {
+        name: Ident,
+        ty: Box<TypeExpr>,
+    },
+    Array {
+        dims: Vec<TypeExpr>,
+        elem: Box<TypeExpr>,
+        packed: bool,
+    },
+    Record(FieldList),
+    String(Option<u8>),
+    Enum(Vec<Ident>),
+    Subrange {
+        range: Range,
+        host: Box<TypeExpr>,
+    },
+    Set(Box<TypeExpr>),
+    File(Option<Box<TypeExpr>>),
+    /// Pointer to a type referred to by name, which may be declared later
+    /// in the same type section.
+    Pointer(Ident),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldDecl {
+    pub names: Vec<Ident>,
+    pub ty: TypeExpr,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Variant {
+    pub labels: Vec<i32>,
+    pub fields: FieldList,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VariantPart {
+    pub tag: Option<Ident>,
+    pub tag_type: TypeExpr,
+    pub variants: Vec<Variant>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldList {
+    pub fixed: Vec<FieldDecl>,
+    pub variant: Option<Box<VariantPart>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LabelSection {
+    pub labels: Vec<Ident>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConstValue {
+    Integer(i32),
+    Real(f64),
+    String(String),
+    /// A value of an enumerated type, `false` and `true` included.
+    Enum {
+        ordinal: i32,
+        ty: TypeExpr,
+    },
+}
+
+impl ConstValue {
+    pub fn is_ordinal(&self) -> bool {
+        matches!(self, ConstValue::Integer(_) | ConstValue::Enum { .. })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConstDecl {
+    pub name: Ident,
+    pub value: ConstValue,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConstSection {
+    pub decls: Vec<ConstDecl>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeDecl {
+    pub name: Ident,
+    pub ty: TypeExpr,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeSection {
+    pub decls: Vec<TypeDecl>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VarDecl {
+    pub names: Vec<Ident>,
+    pub ty: TypeExpr,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VarSection {
+    pub decls: Vec<VarDecl>,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Section {
+    Label(LabelSection),
+    Const(ConstSection),
+    Type(TypeSection),
+    Var(VarSection),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeclarationPart {
+    pub sections: Vec<Section>,
+    pub span: Span,
+}
+
+impl TypeExpr {
+    pub fn resolved(&self) -> &TypeExpr {
+        match self {
+            TypeExpr::Named { ty, .. } => ty.resolved(),
+            ty => ty,
+        }
+    }
+
+    pub fn category(&self) -> TypeCategory {
+        match self.resolved() {
+            TypeExpr::Simple(simple) => simple.category(),
+            TypeExpr::Enum(_) | TypeExpr::Subrange { .. } => TypeCategory::Ordinal,
+            _ => TypeCategory::Other,
+        }
+    }
+
+    pub fn is_ordinal(&self) -> bool {
+        self.category() == TypeCategory::Ordinal
+    }
+
+    /// The type with named types resolved at every level and every
+    /// multi-dimensional array written out as nested one-dimensional arrays,
+    /// so `array[1..2, 1..3] of T` and `array[1..2] of array[1..3] of T`
+    /// expand to the same type.
+    pub fn expanded(&self) -> TypeExpr {
+        match self.resolved() {
+            TypeExpr::Array { dims, elem, packed } => {
+                dims.iter()
+                    .rev()
+                    .fold(elem.expanded(), |elem, index| TypeExpr::Array {
+                        dims: vec![index.expanded()],
+                        elem: Box::new(elem),
+                        packed: *packed,
+                    })
+            }
+            TypeExpr::Record(fields) => TypeExpr::Record(fields.expanded()),
+            TypeExpr::Subrange { range, host } => TypeExpr::Subrange {
+                range: *range,
+                host: Box::new(host.expanded()),
+            },
+            TypeExpr::Set(base) => TypeExpr::Set(Box::new(base.expanded())),
+            TypeExpr::File(elem) => {
+                TypeExpr::File(elem.as_ref().map(|elem| Box::new(elem.expanded())))
+            }
+            ty => ty.clone(),
+        }
+    }
+
+    /// Structural equivalence of the expanded types, ignoring source positions.
+    /// Every enumerated type is distinct, even from one with the same members.
+    pub fn is_equivalent(&self, other: &TypeExpr) -> bool {
+        equivalent(&self.expanded(), &other.expanded())
+    }
+
+    /// Lowest and highest ordinal values of an ordinal type, `None` for any other type.
+    pub fn bounds(&self) -> Option<(i32, i32)> {
+        match self.resolved() {
+            TypeExpr::Simple(simple) => simple.bounds(),
+            TypeExpr::Enum(names) => Some((0, names.len() as i32 - 1)),
+            TypeExpr::Subrange { range, .. } => Some((range.low, range.high)),
+            _ => None,
+        }
+    }
+
+    /// Maximum length of a string type, `None` for any other type.
+    pub fn string_length(&self) -> Option<u8> {
+        match self.resolved() {
+            TypeExpr::String(len) => Some(len.unwrap_or(MAX_STRING_LENGTH)),
+            _ => None,
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            TypeExpr::Named { .. } => format!("{} ({})", self, self.resolved()),
+            ty => ty.to_string(),
+        }
+    }
+}
+
+impl FieldList {
+    fn expanded(&self) -> FieldList {
+        FieldList {
+            fixed: self
+                .fixed
+                .iter()
+                .map(|decl| FieldDecl {
+                    ty: decl.ty.expanded(),
+                    ..decl.clone()
+                })
+                .collect(),
+            variant: self.variant.as_ref().map(|part| {
+                Box::new(VariantPart {
+                    tag_type: part.tag_type.expanded(),
+                    variants: part
+                        .variants
+                        .iter()
+                        .map(|variant| Variant {
+                            fields: variant.fields.expanded(),
+                            ..variant.clone()
+                        })
+                        .collect(),
+                    ..(**part).clone()
+                })
+            }),
+        }
+    }
+}
+
+fn same_name(a: &Ident, b: &Ident) -> bool {
+    a.name.eq_ignore_ascii_case(&b.name)
+}
+
+fn equivalent(a: &TypeExpr, b: &TypeExpr) -> bool {
+    match (a, b) {
+        (
+            TypeExpr::Array {
+                dims: a_dims,
+                elem: a_elem,
+                packed: a_packed,
+            },
+            TypeExpr::Array {
+                dims: b_dims,
+                elem: b_elem,
+                packed: b_packed,
+            },
+        ) => {
+            a_packed == b_packed
+                && a_dims.len() == b_dims.len()
+                && a_dims.iter().zip(b_dims).all(|(a, b)| equivalent(a, b))
+                && equivalent(a_elem, b_elem)
+        }
+        (TypeExpr::Record(a), TypeExpr::Record(b)) => equivalent_fields(a, b),
+        (
+            TypeExpr::Subrange {
+                range: a_range,
+                host: a_host,
+            },
+            TypeExpr::Subrange {
+                range: b_range,
+                host: b_host,
+            },
+        ) => {
+            (a_range.low, a_range.high) == (b_range.low, b_range.high) && equivalent(a_host, b_host)
+        }
+        (TypeExpr::Set(a), TypeExpr::Set(b)) => equivalent(a, b),
+        (TypeExpr::File(a), TypeExpr::File(b)) => match (a, b) {
+            (Some(a), Some(b)) => equivalent(a, b),
+            (a, b) => a.is_none() && b.is_none(),
+        },
+        (TypeExpr::Pointer(a), TypeExpr::Pointer(b)) => same_name(a, b),
+        // Enumerated types compare with their member positions, which tell
+        // apart two declarations with the same member names.
+        (a, b) => a == b,
+    }
+}
+
+fn equivalent_fields(a: &FieldList, b: &FieldList) -> bool {
+    let same_decls = a.fixed.len() == b.fixed.len()
+        && a.fixed.iter().zip(&b.fixed).all(|(a, b)| {
+            a.names.len() == b.names.len()
+                && a.names.iter().zip(&b.names).all(|(a, b)| same_name(a, b))
+                && equivalent(&a.ty, &b.ty)
+        });
+
+    let same_variants = match (&a.variant, &b.variant) {
+        (Some(a), Some(b)) => {
+            let same_tag = match (&a.tag, &b.tag) {
+                (Some(a), Some(b)) => same_name(a, b),
+                (a, b) => a.is_none() && b.is_none(),
+            };
+
+            same_tag
+                && equivalent(&a.tag_type, &b.tag_type)
+                && a.variants.len() == b.variants.len()
+                && a.variants
+                    .iter()
+                    .zip(&b.variants)
+                    .all(|(a, b)| a.labels == b.labels && equivalent_fields(&a.fields, &b.fields))
+        }
+        (a, b) => a.is_none() && b.is_none(),
+    };
+
+    same_decls && same_variants
+}
+
+impl VarSection {
+    pub fn identifiers(&self) -> impl Iterator<Item = (&Ident, &TypeExpr)> {
+        self.decls
+            .iter()
+            .flat_map(|decl| decl.names.iter().map(move |name| (name, &decl.ty)))
+    }
+}
+
+impl DeclarationPart {
+    pub fn var_sections(&self) -> impl Iterator<Item = &VarSection> {
+        self.sections.iter().filter_map(|section| match section {
+            Section::Var(vars) => Some(vars),
+            _ => None,
+        })
+    }
+
+    pub fn var_decls(&self) -> impl Iterator<Item = &VarDecl> {
+        self.var_sections().flat_map(|vars| vars.decls.iter())
+    }
+
+    pub fn identifiers(&self) -> impl Iterator<Item = (&Ident, &TypeExpr)> {
+        self.var_sections().flat_map(|vars| vars.identifiers())
+    }
+
+    pub fn report(&self) -> String {
+        self.identifiers()
+            .map(|(name, ty)| format!("Identifier: {}, type: {}\n", name.name, ty.describe()))
+            .collect()
+    }
+}
+
+impl fmt::Display for ConstValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConstValue::Integer(value) => write!(f, "{}", value),
+            ConstValue::Real(value) => write!(f, "{:?}", value),
+            ConstValue::String(value) => write!(f, "'{}'", value.replace('\'', "''")),
+            ConstValue::Enum { ordinal, ty } => ty.fmt_ordinal(f, *ordinal),
+        }
+    }
+}
+
+impl fmt::Display for Range {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}..{}", self.low, self.high)
+    }
+}
+
+impl TypeExpr {
+    fn fmt_ordinal(&self, f: &mut fmt::Formatter, value: i32) -> fmt::Result {
+        match self.resolved() {
+            TypeExpr::Simple(SimpleType::Char) => {
+                let c = char::from_u32(value as u32).unwrap_or_default();
+                write!(f, "{}", ConstValue::String(c.to_string()))
+            }
+            TypeExpr::Enum(names) => write!(f, "{}", names[value as usize].name),
+            TypeExpr::Simple(SimpleType::Boolean) => write!(f, "{}", value != 0),
+            _ => write!(f, "{}", value),
+        }
+    }
+}
+
+impl fmt::Display for FieldDecl {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let names: Vec<&str> = self.names.iter().map(|name| name.name.as_str()).collect();
+        write!(f, "{}: {}", names.join(", "), self.ty)
+    }
+}
+
+impl fmt::Display for VariantPart {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.tag {
+            Some(tag) => write!(f, "case {}: {} of ", tag.name, self.tag_type)?,
+            None => write!(f, "case {} of ", self.tag_type)?,
+        }
+
+        // Labels are stored as ordinals, the tag type knows how to show them.
+        for (i, variant) in self.variants.iter().enumerate() {
+            if i > 0 {
+                write!(f, "; ")?;
+            }
+            for (j, label) in variant.labels.iter().enumerate() {
+                if j > 0 {
+                    write!(f, ", ")?;
+                }
+                self.tag_type.fmt_ordinal(f, *label)?;
+            }
+            write!(f, ": ({})", variant.fields)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for FieldList {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut parts: Vec<String> = self.fixed.iter().map(|decl| decl.to_string()).collect();
+        if let Some(variant) = &self.variant {
+            parts.push(variant.to_string());
+        }
+
+        write!(f, "{}", parts.join("; "))
+    }
+}
+
+impl fmt::Display for TypeExpr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TypeExpr::Simple(simple) => write!(f, "{}", simple.as_str()),
+            TypeExpr::Named { name, .. } => write!(f, "{}", name.name),
+            TypeExpr::Array { dims, elem, packed } => {
+                if *packed {
+                    write!(f, "packed ")?;
+                }
+                let dims: Vec<String> = dims.iter().map(|index| index.to_string()).collect();
+                write!(f, "array[{}] of {}", dims.join(","), elem)
+            }
+            TypeExpr::Record(fields) if fields.fixed.is_empty() && fields.variant.is_none() => {
+                write!(f, "record end")
+            }
+            TypeExpr::Record(fields) => write!(f, "record {}; end", fields),
+            TypeExpr::String(None) => write!(f, "string"),
+            TypeExpr::String(Some(len)) => write!(f, "string[{}]", len),
+            TypeExpr::Enum(names) => {
+                let names: Vec<&str> = names.iter().map(|name| name.name.as_str()).collect();
+                write!(f, "({})", names.join(", "))
+            }
+            TypeExpr::Subrange { range, host } => {
+                host.fmt_ordinal(f, range.low)?;
+                write!(f, "..")?;
+                host.fmt_ordinal(f, range.high)
+            }
+            TypeExpr::Set(base) => write!(f, "set of {}", base),
+            TypeExpr::File(None) => write!(f, "file"),
+            TypeExpr::File(Some(elem)) => write!(f, "file of {}", elem),
+            TypeExpr::Pointer(target) => write!(f, "^{}", target.name),
+        }
+    }
+}
@@ -1,41 +1,88 @@
-use std::collections::{HashMap, HashSet};
 use std::fmt;
 
+pub mod ast;
+mod parser;
+
+pub use parser::{analyze, analyze_with, Dialect, Options};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    Lexical,
+    Syntax,
+    Semantic,
+}
+
 #[derive(Debug)]
 pub struct LexerError {
+    kind: ErrorKind,
     message: String,
     position: usize,
     token_length: usize,
+    location: Location,
 }
 
 impl fmt::Display for LexerError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "Error at char {}: {}", self.position, self.message)
+        write!(f, "Error at {}: {}", self.location, self.message)
     }
 }
 
 #[allow(dead_code)]
 impl LexerError {
+    fn illegal_character(position: usize, ch: char) -> Self {
+        Self {
+            kind: ErrorKind::Lexical,
+            message: format!("lexical error: illegal character `{}`", ch.escape_debug()),
+            position,
+            token_length: ch.len_utf8(),
+            location: Location::default(),
+        }
+    }
+
+    fn unterminated_comment(position: usize, token_length: usize) -> Self {
+        Self {
+            kind: ErrorKind::Lexical,
+            message: String::from("lexical error: unterminated comment"),
+            position,
+            token_length,
+            location: Location::default(),
+        }
+    }
+
+    fn unterminated_string(position: usize, token_length: usize) -> Self {
+        Self {
+            kind: ErrorKind::Lexical,
+            message: String::from("lexical error: unterminated string literal"),
+            position,
+            token_length,
+            location: Location::default(),
+        }
+    }
+
     fn syntax_error(position: usize, token_length: usize, message: &str) -> Self {
         Self {
+            kind: ErrorKind::Syntax,
             message: format!("syntax_error: {}", message),
             position,
             token_length,
+            location: Location::default(),
         }
     }
 
     fn semantic_error(position: usize, token_length: usize, message: &str) -> Self {
         Self {
+            kind: ErrorKind::Semantic,
             message: format!("semantic error: {}", message),
             position,
             token_length,
+            location: Location::default(),
         }
     }
 
-    fn integer_out_of_range(position: usize, actual: &str) -> Self {
+    fn integer_out_of_range(position: usize, token_length: usize, actual: &str) -> Self {
         LexerError::semantic_error(
             position,
-            actual.len(),
+            token_length,
             format!(
                 "integer constant should be in range [-32768, 32767], actual: {}",
                 actual
@@ -44,69 +91,619 @@ impl LexerError {
         )
     }
 
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// Byte offset of the error in the source.
     pub fn pos(&self) -> usize {
-        return self.position;
+        self.position
     }
 
+    /// Length of the offending text in bytes.
     pub fn tok_length(&self) -> usize {
-        return self.token_length;
+        self.token_length
+    }
+
+    pub fn span(&self) -> Span {
+        Span::new(self.position, self.position + self.token_length)
+    }
+
+    pub fn location(&self) -> Location {
+        self.location
+    }
+
+    fn located(mut self, map: &SourceMap) -> Self {
+        self.location = map.location(self.position);
+        self
+    }
+
+    /// Resolves the location from the tokens the error was found in: every
+    /// parser error starts at the start or the end of some token.
+    fn located_in(mut self, tokens: &[Token]) -> Self {
+        self.location = match tokens
+            .iter()
+            .rev()
+            .find(|tok| tok.span.start <= self.position)
+        {
+            Some(tok) => {
+                let offset = self.position - tok.span.start;
+                let chars = match tok.text.get(..offset) {
+                    Some(text) => text.chars().count(),
+                    None => offset,
+                };
+
+                Location {
+                    line: tok.location.line,
+                    column: tok.location.column + chars,
+                }
+            }
+            None => Location::default(),
+        };
+        self
     }
 }
 
-#[allow(dead_code)]
-#[derive(Debug)]
+/// A 1-based line and column in the source. Columns count chars, not bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Default for Location {
+    fn default() -> Self {
+        Self { line: 1, column: 1 }
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+/// Resolves positions in a source to lines and columns.
+///
+/// A line ends with `\n`, `\r\n` or a lone `\r`.
+#[derive(Debug, Clone)]
+pub struct SourceMap {
+    source: String,
+    line_starts: Vec<usize>,
+}
+
+impl SourceMap {
+    pub fn new(source: &str) -> Self {
+        let bytes = source.as_bytes();
+
+        let mut line_starts = vec![0];
+        for (offset, byte) in bytes.iter().enumerate() {
+            let ends_line = match byte {
+                b'\n' => true,
+                b'\r' => bytes.get(offset + 1) != Some(&b'\n'),
+                _ => false,
+            };
+            if ends_line {
+                line_starts.push(offset + 1);
+            }
+        }
+
+        Self {
+            source: String::from(source),
+            line_starts,
+        }
+    }
+
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// Location of a byte offset; offsets past the end resolve to the end.
+    pub fn location(&self, position: usize) -> Location {
+        let line = self.line_starts.partition_point(|start| *start <= position);
+        let start = self.line_starts[line - 1];
+        let before = Span::new(start, position).slice(&self.source);
+
+        Location {
+            line,
+            column: before.chars().count() + 1,
+        }
+    }
+
+    /// Text of a 1-based line, without its line break.
+    pub fn line_text(&self, line: usize) -> Option<&str> {
+        let start = *self.line_starts.get(line.checked_sub(1)?)?;
+        let end = self
+            .line_starts
+            .get(line)
+            .copied()
+            .unwrap_or(self.source.len());
+
+        Some(self.source[start..end].trim_end_matches(['\r', '\n']))
+    }
+}
+
+/// A range of the source, in byte offsets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize) -> Self {
+        Self { start, end }
+    }
+
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    /// Text of the span in `source`.
+    pub fn slice<'a>(&self, source: &'a str) -> &'a str {
+        self.split(source).1
+    }
+
+    /// Splits `source` into the text before the span, the span itself and the
+    /// text after it. Never panics: the span is clamped to the source and
+    /// widened to whole chars if it doesn't fall on char boundaries.
+    pub fn split<'a>(&self, source: &'a str) -> (&'a str, &'a str, &'a str) {
+        let mut start = self.start.min(source.len());
+        while !source.is_char_boundary(start) {
+            start -= 1;
+        }
+
+        let mut end = self.end.clamp(start, source.len());
+        while !source.is_char_boundary(end) {
+            end += 1;
+        }
+
+        (&source[..start], &source[start..end], &source[end..])
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Keyword {
+    Label,
+    Const,
+    Type,
+    Var,
+    Array,
+    Packed,
+    Set,
+    File,
+    Of,
+    Div,
+    Mod,
+    Record,
+    Case,
+    String,
+    End,
+    Byte,
+    Word,
+    Integer,
+    Real,
+    Char,
+    Double,
+    ShortInt,
+    LongInt,
+    Boolean,
+    Single,
+    Extended,
+    Comp,
+    Pointer,
+    Text,
+}
+
+impl Keyword {
+    pub fn from_word(word: &str) -> Option<Self> {
+        let lower = word.to_lowercase();
+        KEYWORDS
+            .iter()
+            .find(|(kw, _)| *kw == lower)
+            .map(|(_, keyword)| *keyword)
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        KEYWORDS
+            .iter()
+            .find(|(_, keyword)| keyword == self)
+            .map(|(kw, _)| *kw)
+            .unwrap()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Punct {
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    Equal,
+    Less,
+    Greater,
+    LessEqual,
+    GreaterEqual,
+    NotEqual,
+    LeftBracket,
+    RightBracket,
+    Dot,
+    DotDot,
+    Comma,
+    LeftParen,
+    RightParen,
+    Colon,
+    Assign,
+    Semicolon,
+    Caret,
+}
+
+impl Punct {
+    pub fn from_symbol(s: &str) -> Option<Self> {
+        PUNCTS
+            .iter()
+            .find(|(p, _)| *p == s)
+            .map(|(_, punct)| *punct)
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        PUNCTS
+            .iter()
+            .find(|(_, punct)| punct == self)
+            .map(|(p, _)| *p)
+            .unwrap()
+    }
+
+    fn longest_match(chars: &[char]) -> Option<(Self, usize)> {
+        PUNCTS
+            .iter()
+            .filter(|(p, _)| starts_with(chars, p))
+            .map(|(p, punct)| (*punct, p.chars().count()))
+            .max_by_key(|(_, len)| *len)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TokenKind {
+    Keyword(Keyword),
+    Identifier,
+    IntegerLiteral(i32),
+    RealLiteral(f64),
+    StringLiteral,
+    Punct(Punct),
+    /// Only produced by [`tokenize_with_trivia`].
+    Comment,
+    Unknown,
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct Token {
-    word: String,
-    position: usize,
+    kind: TokenKind,
+    text: String,
+    span: Span,
+    location: Location,
 }
 
 impl Token {
-    fn new(word: &str, position: usize) -> Self {
+    fn new(kind: TokenKind, text: &str, span: Span) -> Self {
         Self {
-            word: String::from(word),
-            position,
+            kind,
+            text: String::from(text),
+            span,
+            location: Location::default(),
+        }
+    }
+
+    pub fn kind(&self) -> TokenKind {
+        self.kind
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    pub fn location(&self) -> Location {
+        self.location
+    }
+
+    fn is_punct(&self, punct: Punct) -> bool {
+        self.kind == TokenKind::Punct(punct)
+    }
+
+    fn is_keyword(&self, keyword: Keyword) -> bool {
+        self.kind == TokenKind::Keyword(keyword)
+    }
+
+    pub fn string_value(&self) -> Option<String> {
+        if self.kind != TokenKind::StringLiteral {
+            return None;
         }
+
+        let inner = &self.text[1..self.text.len() - 1];
+        Some(inner.replace("''", "'"))
     }
 }
 
-const MAX_IDENTIFIER_LENGTH: usize = 8;
-const SIMPLE_TYPES: [&'static str; 6] = ["byte", "word", "integer", "real", "char", "double"];
-const KEYWORDS: [&'static str; 9] = [
-    "var", "byte", "word", "integer", "real", "char", "double", "array", "of",
+const KEYWORDS: [(&str, Keyword); 29] = [
+    ("label", Keyword::Label),
+    ("const", Keyword::Const),
+    ("type", Keyword::Type),
+    ("var", Keyword::Var),
+    ("byte", Keyword::Byte),
+    ("word", Keyword::Word),
+    ("integer", Keyword::Integer),
+    ("real", Keyword::Real),
+    ("char", Keyword::Char),
+    ("double", Keyword::Double),
+    ("shortint", Keyword::ShortInt),
+    ("longint", Keyword::LongInt),
+    ("boolean", Keyword::Boolean),
+    ("single", Keyword::Single),
+    ("extended", Keyword::Extended),
+    ("comp", Keyword::Comp),
+    ("pointer", Keyword::Pointer),
+    ("text", Keyword::Text),
+    ("array", Keyword::Array),
+    ("packed", Keyword::Packed),
+    ("set", Keyword::Set),
+    ("file", Keyword::File),
+    ("of", Keyword::Of),
+    ("div", Keyword::Div),
+    ("mod", Keyword::Mod),
+    ("record", Keyword::Record),
+    ("case", Keyword::Case),
+    ("string", Keyword::String),
+    ("end", Keyword::End),
 ];
 
+/// Opening and closing delimiters; `//` comments run to the end of the line.
+const COMMENTS: [(&str, Option<&str>); 3] = [("{", Some("}")), ("(*", Some("*)")), ("//", None)];
+
+const PUNCTS: [(&str, Punct); 21] = [
+    ("+", Punct::Plus),
+    ("-", Punct::Minus),
+    ("*", Punct::Star),
+    ("/", Punct::Slash),
+    ("=", Punct::Equal),
+    ("<", Punct::Less),
+    (">", Punct::Greater),
+    ("<=", Punct::LessEqual),
+    (">=", Punct::GreaterEqual),
+    ("<>", Punct::NotEqual),
+    ("[", Punct::LeftBracket),
+    ("]", Punct::RightBracket),
+    (".", Punct::Dot),
+    ("..", Punct::DotDot),
+    (",", Punct::Comma),
+    ("(", Punct::LeftParen),
+    (")", Punct::RightParen),
+    (":", Punct::Colon),
+    (":=", Punct::Assign),
+    (";", Punct::Semicolon),
+    ("^", Punct::Caret),
+];
+
+fn starts_with(chars: &[char], s: &str) -> bool {
+    s.chars().count() <= chars.len() && s.chars().zip(chars).all(|(a, b)| a == *b)
+}
+
+fn is_word_char(ch: char) -> bool {
+    ch.is_alphanumeric() || ch == '_'
+}
+
+fn is_whitespace(ch: char) -> bool {
+    matches!(ch, ' ' | '\t' | '\x0c') || is_line_break(ch)
+}
+
+fn is_line_break(ch: char) -> bool {
+    matches!(ch, '\n' | '\r')
+}
+
 pub fn tokenize(content: String) -> Result<Vec<Token>, LexerError> {
+    let mut tokens = tokenize_with_trivia(content)?;
+    tokens.retain(|tok| tok.kind != TokenKind::Comment);
+
+    Ok(tokens)
+}
+
+/// Same as [`tokenize`], but keeps comments as [`TokenKind::Comment`] tokens.
+pub fn tokenize_with_trivia(content: String) -> Result<Vec<Token>, LexerError> {
+    let map = SourceMap::new(&content);
+
+    let mut tokens = scan(&content).map_err(|e| e.located(&map))?;
+    for tok in tokens.iter_mut() {
+        tok.location = map.location(tok.span.start);
+    }
+
+    Ok(tokens)
+}
+
+fn scan(content: &str) -> Result<Vec<Token>, LexerError> {
     let mut tokens: Vec<Token> = vec![];
 
-    let mut cur = String::new();
+    // Scanning walks chars, while spans hold the byte offsets of those chars.
+    let chars: Vec<char> = content.chars().collect();
+    let offsets: Vec<usize> = content
+        .char_indices()
+        .map(|(offset, _)| offset)
+        .chain(std::iter::once(content.len()))
+        .collect();
+    let span = |start: usize, end: usize| Span::new(offsets[start], offsets[end]);
+    let mut idx: usize = 0;
+
+    while idx < chars.len() {
+        let ch = chars[idx];
+
+        if is_whitespace(ch) {
+            idx += 1;
+            continue;
+        }
 
-    for (idx, ch) in content.chars().enumerate() {
-        if ch == ' ' {
-            if !cur.is_empty() {
-                tokens.push(Token::new(cur.as_str(), idx - cur.len()));
+        if let Some((open, close)) = COMMENTS
+            .iter()
+            .find(|(open, _)| starts_with(&chars[idx..], open))
+        {
+            let start = idx;
+            idx += open.chars().count();
+            loop {
+                match close {
+                    Some(close) if starts_with(&chars[idx..], close) => {
+                        idx += close.chars().count();
+                        break;
+                    }
+                    Some(_) if idx == chars.len() => {
+                        let span = span(start, start + open.chars().count());
+                        return Err(LexerError::unterminated_comment(span.start, span.len()));
+                    }
+                    None if idx == chars.len() || is_line_break(chars[idx]) => break,
+                    _ => idx += 1,
+                }
             }
-            cur = String::new();
+
+            let span = span(start, idx);
+            tokens.push(Token::new(TokenKind::Comment, span.slice(content), span));
             continue;
-        } else if !ch.is_alphanumeric() {
-            if !cur.is_empty() {
-                tokens.push(Token::new(cur.as_str(), idx - cur.len()));
+        }
+
+        if ch.is_ascii_digit() {
+            let start = idx;
+            let (end, is_real) = scan_number(&chars, idx);
+
+            if end == chars.len() || !is_word_char(chars[end]) {
+                let span = span(start, end);
+                let text = span.slice(content);
+                if is_real {
+                    let value = text.parse::<f64>().unwrap();
+                    tokens.push(Token::new(TokenKind::RealLiteral(value), text, span));
+                } else {
+                    tokens.push(classify_word(text, span)?);
+                }
+
+                idx = end;
+                continue;
             }
-            cur = String::new();
-            tokens.push(Token::new(&ch.to_string(), idx));
         }
 
-        if ch.is_alphanumeric() {
-            cur.push(ch);
+        if ch == '\'' {
+            let start = idx;
+            idx += 1;
+            loop {
+                // A string literal can't span lines.
+                if idx == chars.len() || is_line_break(chars[idx]) {
+                    let span = span(start, idx);
+                    return Err(LexerError::unterminated_string(span.start, span.len()));
+                }
+
+                if chars[idx] == '\'' {
+                    if idx + 1 < chars.len() && chars[idx + 1] == '\'' {
+                        idx += 2;
+                        continue;
+                    }
+                    idx += 1;
+                    break;
+                }
+                idx += 1;
+            }
+
+            let span = span(start, idx);
+            tokens.push(Token::new(
+                TokenKind::StringLiteral,
+                span.slice(content),
+                span,
+            ));
+            continue;
         }
-    }
 
-    if !cur.is_empty() {
-        tokens.push(Token::new(cur.as_str(), content.len() - cur.len()));
+        if is_word_char(ch) {
+            let start = idx;
+            while idx < chars.len() && is_word_char(chars[idx]) {
+                idx += 1;
+            }
+
+            let span = span(start, idx);
+            tokens.push(classify_word(span.slice(content), span)?);
+            continue;
+        }
+
+        match Punct::longest_match(&chars[idx..]) {
+            Some((punct, len)) => {
+                let span = span(idx, idx + len);
+                tokens.push(Token::new(
+                    TokenKind::Punct(punct),
+                    span.slice(content),
+                    span,
+                ));
+                idx += len;
+            }
+            None => return Err(LexerError::illegal_character(offsets[idx], ch)),
+        }
     }
 
     Ok(tokens)
 }
 
+fn scan_number(chars: &[char], start: usize) -> (usize, bool) {
+    let digits_end = |mut idx: usize| {
+        while idx < chars.len() && chars[idx].is_ascii_digit() {
+            idx += 1;
+        }
+        idx
+    };
+    let is_digit_at = |idx: usize| idx < chars.len() && chars[idx].is_ascii_digit();
+
+    let mut idx = digits_end(start);
+    let mut is_real = false;
+
+    if idx < chars.len() && chars[idx] == '.' && is_digit_at(idx + 1) {
+        idx = digits_end(idx + 1);
+        is_real = true;
+    }
+
+    if idx < chars.len() && (chars[idx] == 'e' || chars[idx] == 'E') {
+        let mut exponent = idx + 1;
+        if exponent < chars.len() && (chars[exponent] == '+' || chars[exponent] == '-') {
+            exponent += 1;
+        }
+        if is_digit_at(exponent) {
+            idx = digits_end(exponent);
+            is_real = true;
+        }
+    }
+
+    (idx, is_real)
+}
+
+fn classify_word(word: &str, span: Span) -> Result<Token, LexerError> {
+    let kind = if let Some(keyword) = Keyword::from_word(word) {
+        TokenKind::Keyword(keyword)
+    } else if is_identifier(word) {
+        TokenKind::Identifier
+    } else if is_integer(word) {
+        match word.parse::<i32>() {
+            Ok(value) => TokenKind::IntegerLiteral(value),
+            Err(_) => {
+                return Err(LexerError::integer_out_of_range(
+                    span.start,
+                    span.len(),
+                    word,
+                ))
+            }
+        }
+    } else {
+        TokenKind::Unknown
+    };
+
+    Ok(Token::new(kind, word, span))
+}
+
 pub fn is_integer(str: &str) -> bool {
     #[derive(Debug, PartialEq)]
     enum State {
@@ -162,7 +759,7 @@ pub fn is_integer(str: &str) -> bool {
         i += 1;
     }
 
-    return state == State::Finish && i == str.len() + 1;
+    state == State::Finish && i == str.chars().count() + 1
 }
 
 pub fn is_identifier(str: &str) -> bool {
@@ -181,7 +778,7 @@ pub fn is_identifier(str: &str) -> bool {
         match str.chars().nth(i) {
             Some(ch) => match state {
                 State::Start => {
-                    if ch.is_alphabetic() {
+                    if ch.is_ascii_alphabetic() || ch == '_' {
                         state = State::Chars;
                     } else {
                         state = State::Error;
@@ -189,7 +786,7 @@ pub fn is_identifier(str: &str) -> bool {
                     }
                 }
                 State::Chars => {
-                    if ch.is_alphabetic() || ch.is_ascii_digit() {
+                    if ch.is_ascii_alphanumeric() || ch == '_' {
                         state = State::Chars;
                     } else {
                         state = State::Error;
@@ -209,415 +806,5 @@ pub fn is_identifier(str: &str) -> bool {
         i += 1;
     }
 
-    return state == State::Finish && i == str.len() + 1;
-}
-
-pub fn analyze(tokens: Vec<Token>) -> Result<HashMap<String, String>, LexerError> {
-    #[derive(Debug, PartialEq)]
-    enum State {
-        Start,
-        Definition,
-        Identifier,
-        Type,
-        SimpleType,
-        Array,
-        RangesStart,
-        RangesEnd,
-        FirstRangeBeginValue,
-        FirstRangeDelimiter,
-        FirstRangeEndValue,
-        RangesDelimiter,
-        SecondRangeBeginValue,
-        SecondRangeDelimiter,
-        SecondRangeEndValue,
-        Of,
-        ArrayType,
-        Error,
-        Finish,
-    }
-
-    let mut identifiers: HashMap<String, String> = HashMap::new();
-    let mut pending_identifiers: HashSet<String> = HashSet::new();
-    let mut state = State::Start;
-    let mut i: usize = 0;
-
-    let mut collecting_array_type = false;
-    let mut array_type = String::new();
-
-    let mut range_left_bound = 0;
-
-    while (state != State::Error) && (state != State::Finish) {
-        match tokens.get(i) {
-            None => {
-                if tokens.is_empty() {
-                    return Err(LexerError::syntax_error(
-                        0,
-                        0, // TODO:
-                        "var declaration should starts with VAR keyword",
-                    ));
-                }
-
-                if let Some(last) = tokens.get(tokens.len() - 1) {
-                    return Err(LexerError::syntax_error(
-                        last.position + last.word.len(),
-                        1,
-                        "expected semicolon at the end",
-                    ));
-                }
-            }
-            Some(tok) => {
-                let word = &tok.word.clone();
-                let word_lower = &word.to_lowercase();
-
-                if collecting_array_type {
-                    array_type.push_str(word);
-                    if word_lower == "of" {
-                        array_type.push(' ');
-                    }
-                }
-
-                match state {
-                    State::Start => {
-                        if word_lower == "var" {
-                            state = State::Definition;
-                        } else {
-                            return Err(LexerError::syntax_error(
-                                tok.position,
-                                tok.word.len(),
-                                format!("expected `var`, found `{}`", word).as_str(),
-                            ));
-                        }
-                    }
-                    State::Definition => {
-                        if is_identifier(word) {
-                            if word.len() > MAX_IDENTIFIER_LENGTH {
-                                return Err(LexerError::semantic_error(
-                                    tok.position,
-                                    tok.word.len(),
-                                    "identifier can't be longer than 8 characters",
-                                ));
-                            }
-                            if is_keyword(word_lower) {
-                                return Err(LexerError::semantic_error(
-                                    tok.position,
-                                    tok.word.len(),
-                                    "identifier can't reserved word: var, real, double, etc.",
-                                ));
-                            }
-
-                            if !pending_identifiers.insert(String::from(word))
-                                || identifiers.contains_key(word_lower)
-                            {
-                                return Err(LexerError::semantic_error(
-                                    tok.position,
-                                    tok.word.len(),
-                                    format!("identifier `{}` already taken", word).as_str(),
-                                ));
-                            }
-
-                            state = State::Identifier;
-                        } else {
-                            return Err(LexerError::syntax_error(
-                                tok.position,
-                                tok.word.len(),
-                                format!("`{}` should be a valid identifier", word).as_str(),
-                            ));
-                        }
-                    }
-                    State::Identifier => {
-                        if word == "," {
-                            state = State::Definition
-                        } else if word == ":" {
-                            state = State::Type;
-                        } else {
-                            return Err(LexerError::syntax_error(
-                                tok.position,
-                                tok.word.len(),
-                                format!("`{}` should be either comma or colon", word).as_str(),
-                            ));
-                        }
-                    }
-                    State::Type => {
-                        if is_simple_type(word_lower) {
-                            for identifier in pending_identifiers.iter() {
-                                identifiers.insert(identifier.clone(), word_lower.clone());
-                            }
-
-                            pending_identifiers = HashSet::new();
-
-                            state = State::SimpleType;
-                        } else if word_lower == "array" {
-                            collecting_array_type = true;
-                            array_type.push_str(word);
-
-                            state = State::Array;
-                        } else {
-                            return Err(LexerError::syntax_error(
-                                tok.position,
-                                tok.word.len(),
-                                format!(
-                                "`{}` should be a valid type keyword: byte, word, integer, etc.",
-                                word
-                            )
-                                .as_str(),
-                            ));
-                        }
-                    }
-                    State::SimpleType => {
-                        if word == "," {
-                            state = State::Definition;
-                        } else if word == ";" {
-                            state = State::Finish;
-                        } else {
-                            return Err(LexerError::syntax_error(
-                                tok.position,
-                                tok.word.len(),
-                                format!("`{}` should be either comma or semicolon", word).as_str(),
-                            ));
-                        }
-                    }
-                    State::Array => {
-                        if word == "[" {
-                            state = State::RangesStart;
-                        } else {
-                            return Err(LexerError::syntax_error(
-                                tok.position,
-                                tok.word.len(),
-                                format!("expected `[`, found `{}`", word).as_str(),
-                            ));
-                        }
-                    }
-                    State::RangesStart => {
-                        if is_integer(word) {
-                            if !is_integer_in_range(word) {
-                                return Err(LexerError::integer_out_of_range(tok.position, word));
-                            }
-
-                            if let Ok(value) = word.parse::<i16>() {
-                                range_left_bound = value;
-                            } else {
-                                unreachable!()
-                            }
-
-                            state = State::FirstRangeBeginValue;
-                        } else {
-                            return Err(LexerError::syntax_error(
-                                tok.position,
-                                tok.word.len(),
-                                format!(
-                                    "expected integer constant (start of a range), found `{}`",
-                                    word
-                                )
-                                .as_str(),
-                            ));
-                        }
-                    }
-                    State::FirstRangeBeginValue => {
-                        if word == ":" {
-                            state = State::FirstRangeDelimiter;
-                        } else {
-                            return Err(LexerError::syntax_error(
-                                tok.position,
-                                tok.word.len(),
-                                format!("expected `:`, found `{}`", word).as_str(),
-                            ));
-                        }
-                    }
-                    State::FirstRangeDelimiter => {
-                        if is_integer(word) {
-                            if !is_integer_in_range(word) {
-                                return Err(LexerError::integer_out_of_range(tok.position, word));
-                            }
-
-                            if let Ok(value) = word.parse::<i16>() {
-                                if value <= range_left_bound {
-                                    return Err(LexerError::semantic_error(
-                                        tok.position,
-                                        tok.word.len(),
-                                        format!("first bound of range should be less than second")
-                                            .as_str(),
-                                    ));
-                                }
-                            } else {
-                                unreachable!()
-                            }
-
-                            state = State::FirstRangeEndValue;
-                        } else {
-                            return Err(LexerError::syntax_error(
-                                tok.position,
-                                tok.word.len(),
-                                format!(
-                                    "unexpected token: expected integer constant, found `{}`",
-                                    word
-                                )
-                                .as_str(),
-                            ));
-                        }
-                    }
-                    State::FirstRangeEndValue => {
-                        if word == "," {
-                            state = State::RangesDelimiter;
-                        } else if word == "]" {
-                            state = State::RangesEnd;
-                        } else {
-                            return Err(LexerError::syntax_error(
-                                tok.position,
-                                tok.word.len(),
-                                format!("unexpected token: expected `,`, found `{}`", word)
-                                    .as_str(),
-                            ));
-                        }
-                    }
-                    State::RangesDelimiter => {
-                        if is_integer(word) {
-                            if !is_integer_in_range(word) {
-                                return Err(LexerError::integer_out_of_range(tok.position, word));
-                            }
-
-                            if let Ok(value) = word.parse::<i16>() {
-                                range_left_bound = value;
-                            } else {
-                                unreachable!()
-                            }
-
-                            state = State::SecondRangeBeginValue;
-                        } else {
-                            return Err(LexerError::syntax_error(
-                                tok.position,
-                                tok.word.len(),
-                                format!("expected integer constant, found `{}`", word).as_str(),
-                            ));
-                        }
-                    }
-                    State::SecondRangeBeginValue => {
-                        if word == ":" {
-                            state = State::SecondRangeDelimiter;
-                        } else {
-                            return Err(LexerError::syntax_error(
-                                tok.position,
-                                tok.word.len(),
-                                format!("expected `:`, found `{}`", word).as_str(),
-                            ));
-                        }
-                    }
-                    State::SecondRangeDelimiter => {
-                        if is_integer(word) {
-                            if !is_integer_in_range(word) {
-                                return Err(LexerError::integer_out_of_range(tok.position, word));
-                            }
-
-                            if let Ok(value) = word.parse::<i16>() {
-                                if value <= range_left_bound {
-                                    return Err(LexerError::semantic_error(
-                                        tok.position,
-                                        tok.word.len(),
-                                        format!("first bound of range should be less than second")
-                                            .as_str(),
-                                    ));
-                                }
-                            } else {
-                                unreachable!()
-                            }
-
-                            state = State::SecondRangeEndValue;
-                        } else {
-                            return Err(LexerError::syntax_error(
-                                tok.position,
-                                tok.word.len(),
-                                format!("expected integer constant, found `{}`", word).as_str(),
-                            ));
-                        }
-                    }
-                    State::SecondRangeEndValue => {
-                        if word == "]" {
-                            state = State::RangesEnd;
-                        } else {
-                            return Err(LexerError::syntax_error(
-                                tok.position,
-                                tok.word.len(),
-                                format!("expected `]`, found `{}`", word).as_str(),
-                            ));
-                        }
-                    }
-                    State::RangesEnd => {
-                        if word_lower == "of" {
-                            state = State::Of;
-                        } else {
-                            return Err(LexerError::syntax_error(
-                                tok.position,
-                                tok.word.len(),
-                                format!("expected `of`, found `{}`", word).as_str(),
-                            ));
-                        }
-                    }
-                    State::Of => {
-                        if is_simple_type(word_lower) {
-                            for identifier in pending_identifiers.iter() {
-                                identifiers.insert(identifier.clone(), array_type.to_string());
-                            }
-
-                            pending_identifiers = HashSet::new();
-                            array_type = String::new();
-                            collecting_array_type = false;
-
-                            state = State::ArrayType;
-                        } else {
-                            return Err(LexerError::syntax_error(
-                                tok.position,
-                                tok.word.len(),
-                                format!("expected on of simple types (byte, integer, real, etc), found `{}`", word).as_str(),
-                            ));
-                        }
-                    }
-                    State::ArrayType => {
-                        if word == ";" {
-                            state = State::Finish;
-                        } else if word == "," {
-                            state = State::Definition;
-                        } else {
-                            return Err(LexerError::syntax_error(
-                                tok.position,
-                                tok.word.len(),
-                                format!("expected `:` or `,`, found `{}`", word).as_str(),
-                            ));
-                        }
-                    }
-                    _ => {}
-                }
-            }
-        }
-        i += 1;
-    }
-
-    for (identifier, typ) in &identifiers {
-        println!("Identifier: {}, type: {}", identifier, typ);
-    }
-
-    Ok(identifiers)
-}
-
-fn is_integer_in_range(s: &str) -> bool {
-    match s.parse::<i32>() {
-        Ok(value) => value >= -32768 && value <= 32767,
-        Err(_) => false,
-    }
-}
-
-fn is_simple_type(s: &str) -> bool {
-    for kw in SIMPLE_TYPES.iter() {
-        if *kw == s {
-            return true;
-        }
-    }
-    return false;
-}
-
-fn is_keyword(s: &str) -> bool {
-    for kw in KEYWORDS.iter() {
-        if *kw == s {
-            return true;
-        }
-    }
-    return false;
+    state == State::Finish && i == str.chars().count() + 1
 }
@@ -1,4 +1,4 @@
-use analyzer::{analyze, tokenize};
+use analyzer::{analyze, tokenize, LexerError};
 use iced::widget::{button, column, row, text, text_input, Column};
 
 #[derive(Default)]
@@ -20,8 +20,8 @@ pub enum Message {
 }
 
 impl App {
-    pub fn view(&self) -> Column<Message> {
-        let title = text("Turbo Pascal VAR analyzer").size(24);
+    pub fn view(&self) -> Column<'_, Message> {
+        let title = text("Turbo Pascal declaration part analyzer").size(24);
 
         let input = text_input("Source", &self.source)
             .id("source")
@@ -51,7 +51,7 @@ impl App {
 
         const SPACING: u16 = 20;
         if !self.error.is_empty() {
-            return Column::new()
+            Column::new()
                 .padding(20)
                 .spacing(SPACING)
                 .push(title)
@@ -59,9 +59,8 @@ impl App {
                 .push(process_button)
                 .push(error_message)
                 .push(report)
-                .into();
         } else {
-            return Column::new()
+            Column::new()
                 // .align_x(Center)
                 .padding(20)
                 .spacing(SPACING)
@@ -69,7 +68,7 @@ impl App {
                 .push(input)
                 .push(process_button)
                 .push(success_message)
-                .push(idents_table);
+                .push(idents_table)
         }
     }
 
@@ -81,40 +80,53 @@ impl App {
             }
             Message::Process => match tokenize(self.source.clone()) {
                 Ok(tokens) => match analyze(tokens) {
-                    Ok(identifiers) => {
+                    Ok(part) => {
                         self.success = format!(
-                            "String `{}` is a valid Turbo Pascal var declaration",
+                            "String `{}` is a valid Turbo Pascal declaration part",
                             self.source
                         );
                         self.error = String::new();
 
-                        for (identifier, typ) in &identifiers {
+                        for decl in part.var_decls() {
+                            let names: Vec<&str> =
+                                decl.names.iter().map(|name| name.name.as_str()).collect();
+                            let label = if names.len() == 1 {
+                                "Identifier"
+                            } else {
+                                "Identifiers"
+                            };
+
                             self.identifiers.push_str(
-                                format!("Identifier: {}, type: {}\n", identifier, typ).as_str(),
+                                format!(
+                                    "{}: {}, type: {}\n",
+                                    label,
+                                    names.join(", "),
+                                    decl.ty.describe()
+                                )
+                                .as_str(),
                             );
                         }
                     }
-                    Err(e) => {
-                        self.error = format!("{}", e);
-
-                        self.before_error = self.source[0..e.pos()].to_string();
-                        if e.pos() != self.source.len() {
-                            self.error_token =
-                                self.source[e.pos()..e.pos() + e.tok_length()].to_string();
-                            self.after_error = self.source
-                                [e.pos() + e.tok_length()..self.source.len()]
-                                .to_string();
-                        } else {
-                            self.error_token = String::from(";");
-                            self.after_error = String::new();
-                        }
-                    }
+                    Err(e) => self.report_error(e),
                 },
-                Err(e) => self.error = format!("{}", e),
+                Err(e) => self.report_error(e),
             },
         }
     }
 
+    fn report_error(&mut self, e: LexerError) {
+        self.error = format!("{}", e);
+
+        let (before, token, after) = e.span().split(&self.source);
+        self.before_error = before.to_string();
+        self.after_error = after.to_string();
+        self.error_token = if token.is_empty() && after.is_empty() {
+            String::from(";")
+        } else {
+            token.to_string()
+        };
+    }
+
     pub fn clear(&mut self) {
         self.success = String::new();
         self.error = String::new();
@@ -128,7 +140,11 @@ impl App {
 }
 
 fn main() -> iced::Result {
-    iced::run("Analyzer | Turbo Pascal VAR", App::update, App::view)
+    iced::run(
+        "Analyzer | Turbo Pascal declaration part",
+        App::update,
+        App::view,
+    )
 }
 
 // use analyzer::{analyze, tokenize};
@@ -138,11 +154,12 @@ fn main() -> iced::Result {
 
 //     match tokenize(content.clone()) {
 //         Ok(tokens) => match analyze(tokens) {
-//             Ok(()) => {
+//             Ok(section) => {
 //                 println!(
 //                     "String `{}` is a valid Turbo Pascal var declaration",
 //                     content
 //                 );
+//                 print!("{}", section.report());
 //             }
 //             Err(e) => {
 //                 println!("{}", e)
@@ -0,0 +1,1286 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::ast::{
+    ConstDecl, ConstSection, ConstValue, DeclarationPart, FieldDecl, FieldList, Ident,
+    LabelSection, Range, Section, SimpleType, TypeDecl, TypeExpr, TypeSection, VarDecl, VarSection,
+    Variant, VariantPart, MAX_STRING_LENGTH,
+};
+use crate::{Keyword, LexerError, Punct, Span, Token, TokenKind};
+
+const MAX_IDENTIFIER_LENGTH: usize = 8;
+const DEFAULT_MAX_DIMENSIONS: usize = 16;
+const MAX_LABEL: i32 = 9999;
+const MAX_ENUM_VALUES: usize = 256;
+const MAX_SET_ORDINAL: i32 = 255;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Dialect {
+    Course,
+    Standard,
+    #[default]
+    Lenient,
+}
+
+impl Dialect {
+    fn is_range_separator(&self, tok: &Token) -> bool {
+        match self {
+            Dialect::Course => tok.is_punct(Punct::Colon),
+            Dialect::Standard => tok.is_punct(Punct::DotDot),
+            Dialect::Lenient => tok.is_punct(Punct::Colon) || tok.is_punct(Punct::DotDot),
+        }
+    }
+
+    fn range_separator_hint(&self) -> &'static str {
+        match self {
+            Dialect::Course => "`:`",
+            Dialect::Standard => "`..`",
+            Dialect::Lenient => "`..` or `:`",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Options {
+    pub dialect: Dialect,
+    pub max_dimensions: usize,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            dialect: Dialect::default(),
+            max_dimensions: DEFAULT_MAX_DIMENSIONS,
+        }
+    }
+}
+
+pub fn analyze(tokens: Vec<Token>) -> Result<DeclarationPart, LexerError> {
+    analyze_with(tokens, &Options::default())
+}
+
+pub fn analyze_with(tokens: Vec<Token>, options: &Options) -> Result<DeclarationPart, LexerError> {
+    let tokens: Vec<Token> = tokens
+        .into_iter()
+        .filter(|tok| tok.kind != TokenKind::Comment)
+        .collect();
+
+    let mut parser = Parser::new(&tokens, options);
+    parser
+        .parse_declaration_part()
+        .map_err(|e| e.located_in(&tokens))
+}
+
+struct Parser<'a> {
+    tokens: &'a [Token],
+    pos: usize,
+    options: &'a Options,
+    declared: HashSet<String>,
+    constants: HashMap<String, ConstValue>,
+    types: HashMap<String, TypeExpr>,
+    enumerated: HashMap<String, (i32, TypeExpr)>,
+    forward: Option<Vec<&'a Token>>,
+    defining: Option<String>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(tokens: &'a [Token], options: &'a Options) -> Self {
+        let mut declared = HashSet::new();
+        let mut enumerated = HashMap::new();
+        for (ordinal, name) in ["false", "true"].into_iter().enumerate() {
+            declared.insert(String::from(name));
+            enumerated.insert(
+                String::from(name),
+                (ordinal as i32, TypeExpr::Simple(SimpleType::Boolean)),
+            );
+        }
+
+        Self {
+            tokens,
+            pos: 0,
+            options,
+            declared,
+            constants: HashMap::new(),
+            types: HashMap::new(),
+            enumerated,
+            forward: None,
+            defining: None,
+        }
+    }
+
+    fn peek(&self) -> Option<&'a Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn advance(&mut self, expected: &str) -> Result<&'a Token, LexerError> {
+        match self.tokens.get(self.pos) {
+            Some(tok) => {
+                self.pos += 1;
+                Ok(tok)
+            }
+            None => Err(self.end_of_input(expected)),
+        }
+    }
+
+    fn end_of_input(&self, expected: &str) -> LexerError {
+        match self.tokens.last() {
+            Some(last) => LexerError::syntax_error(
+                last.span.end,
+                1,
+                format!("expected {}, found end of input", expected).as_str(),
+            ),
+            None => LexerError::syntax_error(
+                0,
+                0,
+                "declaration part should start with `label`, `const`, `type` or `var`",
+            ),
+        }
+    }
+
+    fn last_end(&self) -> usize {
+        self.tokens[self.pos - 1].span.end
+    }
+
+    fn at_identifier(&self) -> bool {
+        matches!(self.peek(), Some(tok) if tok.kind == TokenKind::Identifier)
+    }
+
+    fn expect_punct(&mut self, punct: Punct) -> Result<&'a Token, LexerError> {
+        let expected = format!("`{}`", punct.as_str());
+        let tok = self.advance(&expected)?;
+        if !tok.is_punct(punct) {
+            return Err(syntax_error(
+                tok,
+                format!("expected {}, found `{}`", expected, tok.text()).as_str(),
+            ));
+        }
+
+        Ok(tok)
+    }
+
+    fn parse_declaration_part(&mut self) -> Result<DeclarationPart, LexerError> {
+        let mut sections = vec![];
+        while let Some(tok) = self.peek() {
+            let section = match tok.kind {
+                TokenKind::Keyword(Keyword::Label) => Section::Label(self.parse_label_section()?),
+                TokenKind::Keyword(Keyword::Const) => Section::Const(self.parse_const_section()?),
+                TokenKind::Keyword(Keyword::Type) => Section::Type(self.parse_type_section()?),
+                TokenKind::Keyword(Keyword::Var) => Section::Var(self.parse_var_section()?),
+                _ if sections.is_empty() => {
+                    return Err(syntax_error(
+                        tok,
+                        format!(
+                            "expected `label`, `const`, `type` or `var`, found `{}`",
+                            tok.text()
+                        )
+                        .as_str(),
+                    ))
+                }
+                _ => {
+                    return Err(syntax_error(
+                        tok,
+                        format!("unexpected `{}` after the last declaration", tok.text()).as_str(),
+                    ))
+                }
+            };
+            sections.push(section);
+        }
+
+        if sections.is_empty() {
+            return Err(self.end_of_input("declaration"));
+        }
+
+        Ok(DeclarationPart {
+            sections,
+            span: Span::new(self.tokens[0].span.start, self.last_end()),
+        })
+    }
+
+    fn parse_label_section(&mut self) -> Result<LabelSection, LexerError> {
+        let keyword = self.advance("`label`")?;
+
+        let mut labels = vec![];
+        loop {
+            labels.push(self.parse_label()?);
+
+            let tok = self.advance("`;`")?;
+            if tok.is_punct(Punct::Semicolon) {
+                break;
+            } else if !tok.is_punct(Punct::Comma) {
+                return Err(syntax_error(
+                    tok,
+                    format!("`{}` should be either comma or semicolon", tok.text()).as_str(),
+                ));
+            }
+        }
+
+        Ok(LabelSection {
+            labels,
+            span: Span::new(keyword.span.start, self.last_end()),
+        })
+    }
+
+    fn parse_label(&mut self) -> Result<Ident, LexerError> {
+        let tok = self.advance("label")?;
+        match tok.kind {
+            TokenKind::IntegerLiteral(value) => {
+                if !(0..=MAX_LABEL).contains(&value) {
+                    return Err(semantic_error(
+                        tok,
+                        format!("label should be in range [0, {}]", MAX_LABEL).as_str(),
+                    ));
+                }
+
+                if !self.declared.insert(value.to_string()) {
+                    return Err(semantic_error(
+                        tok,
+                        format!("label `{}` already declared", tok.text()).as_str(),
+                    ));
+                }
+
+                Ok(Ident {
+                    name: String::from(tok.text()),
+                    span: tok.span,
+                })
+            }
+            TokenKind::Identifier | TokenKind::Keyword(_) => self.declare(tok),
+            _ => Err(syntax_error(
+                tok,
+                format!("`{}` should be a valid label", tok.text()).as_str(),
+            )),
+        }
+    }
+
+    fn parse_const_section(&mut self) -> Result<ConstSection, LexerError> {
+        let keyword = self.advance("`const`")?;
+
+        let mut decls = vec![];
+        loop {
+            decls.push(self.parse_const_decl()?);
+            self.expect_punct(Punct::Semicolon)?;
+
+            if !self.at_identifier() {
+                break;
+            }
+        }
+
+        Ok(ConstSection {
+            decls,
+            span: Span::new(keyword.span.start, self.last_end()),
+        })
+    }
+
+    fn parse_const_decl(&mut self) -> Result<ConstDecl, LexerError> {
+        let name = self.parse_new_ident()?;
+        self.expect_punct(Punct::Equal)?;
+        let value = self.parse_const_value()?;
+        if let ConstValue::Enum { ordinal, ty } = &value {
+            self.enumerated
+                .insert(name.name.to_lowercase(), (*ordinal, ty.clone()));
+        }
+        self.constants
+            .insert(name.name.to_lowercase(), value.clone());
+
+        Ok(ConstDecl {
+            span: Span::new(name.span.start, self.last_end()),
+            name,
+            value,
+        })
+    }
+
+    fn parse_const_value(&mut self) -> Result<ConstValue, LexerError> {
+        let mut tok = self.advance("constant")?;
+
+        let mut sign = None;
+        if tok.is_punct(Punct::Minus) || tok.is_punct(Punct::Plus) {
+            sign = Some(tok);
+            tok = self.advance("constant")?;
+        }
+
+        let value = match tok.kind {
+            TokenKind::IntegerLiteral(value) => ConstValue::Integer(value),
+            TokenKind::RealLiteral(value) => ConstValue::Real(value),
+            TokenKind::StringLiteral => ConstValue::String(tok.string_value().unwrap()),
+            TokenKind::Identifier => match self.enumerated.get(&tok.text().to_lowercase()) {
+                Some((ordinal, ty)) => ConstValue::Enum {
+                    ordinal: *ordinal,
+                    ty: ty.clone(),
+                },
+                None => self.lookup_constant(tok)?.clone(),
+            },
+            _ => {
+                return Err(syntax_error(
+                    tok,
+                    format!("expected constant, found `{}`", tok.text()).as_str(),
+                ))
+            }
+        };
+
+        match (sign, value) {
+            (_, ConstValue::Integer(value)) => {
+                Ok(ConstValue::Integer(integer_constant(sign, tok, value)?.0))
+            }
+            (Some(sign), ConstValue::Real(value)) if sign.is_punct(Punct::Minus) => {
+                Ok(ConstValue::Real(-value))
+            }
+            (Some(sign), ConstValue::String(_)) => Err(LexerError::semantic_error(
+                sign.span.start,
+                tok.span.end - sign.span.start,
+                "sign can't be applied to a string constant",
+            )),
+            (Some(sign), ConstValue::Enum { .. }) => Err(LexerError::semantic_error(
+                sign.span.start,
+                tok.span.end - sign.span.start,
+                "sign can't be applied to an enumerated constant",
+            )),
+            (_, value) => Ok(value),
+        }
+    }
+
+    fn lookup_constant(&self, tok: &Token) -> Result<&ConstValue, LexerError> {
+        let key = tok.text().to_lowercase();
+        match self.constants.get(&key) {
+            Some(value) => Ok(value),
+            None if self.declared.contains(&key) => Err(semantic_error(
+                tok,
+                format!("`{}` is not a constant", tok.text()).as_str(),
+            )),
+            None => Err(semantic_error(
+                tok,
+                format!("unknown constant `{}`", tok.text()).as_str(),
+            )),
+        }
+    }
+
+    fn parse_type_section(&mut self) -> Result<TypeSection, LexerError> {
+        let keyword = self.advance("`type`")?;
+
+        let mut decls = vec![];
+        self.forward = Some(vec![]);
+        loop {
+            decls.push(self.parse_type_decl()?);
+            self.expect_punct(Punct::Semicolon)?;
+
+            if !self.at_identifier() {
+                break;
+            }
+        }
+
+        // Pointer targets may be declared after the pointer type, but only
+        // within the same section.
+        for tok in self.forward.take().unwrap_or_default() {
+            self.lookup_type(tok)?;
+        }
+
+        Ok(TypeSection {
+            decls,
+            span: Span::new(keyword.span.start, self.last_end()),
+        })
+    }
+
+    fn parse_type_decl(&mut self) -> Result<TypeDecl, LexerError> {
+        let name = self.parse_new_ident()?;
+        self.expect_punct(Punct::Equal)?;
+
+        self.defining = Some(name.name.to_lowercase());
+        let ty = self.parse_type();
+        self.defining = None;
+
+        let ty = ty?;
+        self.types.insert(name.name.to_lowercase(), ty.clone());
+
+        Ok(TypeDecl {
+            span: Span::new(name.span.start, self.last_end()),
+            name,
+            ty,
+        })
+    }
+
+    fn parse_var_section(&mut self) -> Result<VarSection, LexerError> {
+        let keyword = self.advance("`var`")?;
+
+        let mut decls = vec![];
+        loop {
+            decls.push(self.parse_var_decl()?);
+
+            let tok = self.advance("`;`")?;
+            if tok.is_punct(Punct::Semicolon) {
+                if !self.at_identifier() {
+                    break;
+                }
+            } else if !tok.is_punct(Punct::Comma) {
+                return Err(syntax_error(
+                    tok,
+                    format!("`{}` should be either comma or semicolon", tok.text()).as_str(),
+                ));
+            }
+        }
+
+        Ok(VarSection {
+            decls,
+            span: Span::new(keyword.span.start, self.last_end()),
+        })
+    }
+
+    fn parse_var_decl(&mut self) -> Result<VarDecl, LexerError> {
+        let mut names = vec![];
+        loop {
+            names.push(self.parse_new_ident()?);
+
+            let tok = self.advance("`,` or `:`")?;
+            if tok.is_punct(Punct::Colon) {
+                break;
+            } else if !tok.is_punct(Punct::Comma) {
+                return Err(syntax_error(
+                    tok,
+                    format!("`{}` should be either comma or colon", tok.text()).as_str(),
+                ));
+            }
+        }
+
+        let ty = self.parse_type()?;
+
+        Ok(VarDecl {
+            span: Span::new(names[0].span.start, self.last_end()),
+            names,
+            ty,
+        })
+    }
+
+    fn parse_new_ident(&mut self) -> Result<Ident, LexerError> {
+        let tok = self.advance("identifier")?;
+        self.declare(tok)
+    }
+
+    fn declare(&mut self, tok: &Token) -> Result<Ident, LexerError> {
+        let ident = ident(tok)?;
+        if !self.declared.insert(ident.name.to_lowercase()) {
+            return Err(semantic_error(
+                tok,
+                format!("identifier `{}` already taken", tok.text()).as_str(),
+            ));
+        }
+
+        Ok(ident)
+    }
+
+    fn parse_type(&mut self) -> Result<TypeExpr, LexerError> {
+        if self.at_subrange() {
+            return self.parse_subrange(false);
+        }
+
+        let tok = self.advance("type")?;
+        match tok.kind {
+            TokenKind::Punct(Punct::LeftParen) => self.parse_enum(tok),
+            TokenKind::Keyword(Keyword::Array) => self.parse_array(false),
+            TokenKind::Keyword(Keyword::Packed) => {
+                let tok = self.advance("`array`")?;
+                if !tok.is_keyword(Keyword::Array) {
+                    return Err(syntax_error(
+                        tok,
+                        format!("expected `array` after `packed`, found `{}`", tok.text()).as_str(),
+                    ));
+                }
+
+                self.parse_array(true)
+            }
+            TokenKind::Keyword(Keyword::Record) => self.parse_record(),
+            TokenKind::Keyword(Keyword::String) => self.parse_string(),
+            TokenKind::Keyword(Keyword::Set) => self.parse_set(),
+            TokenKind::Keyword(Keyword::File) => self.parse_file(),
+            TokenKind::Punct(Punct::Caret) => self.parse_pointer(),
+            TokenKind::Keyword(keyword) if SimpleType::from_keyword(keyword).is_some() => {
+                Ok(TypeExpr::Simple(SimpleType::from_keyword(keyword).unwrap()))
+            }
+            TokenKind::Identifier => self.lookup_type(tok),
+            _ => Err(syntax_error(
+                tok,
+                format!(
+                    "`{}` should be a valid type: byte, word, integer, a type name, etc.",
+                    tok.text()
+                )
+                .as_str(),
+            )),
+        }
+    }
+
+    fn at_subrange(&self) -> bool {
+        let Some(tok) = self.peek() else {
+            return false;
+        };
+
+        match tok.kind {
+            TokenKind::IntegerLiteral(_) | TokenKind::StringLiteral => true,
+            TokenKind::Punct(Punct::Minus | Punct::Plus) => true,
+            // `(` starts an enumerated type only as `(ident,` or `(ident)`,
+            // otherwise it opens a parenthesised bound.
+            TokenKind::Punct(Punct::LeftParen) => !matches!(
+                (self.tokens.get(self.pos + 1), self.tokens.get(self.pos + 2)),
+                (Some(name), Some(next)) if name.kind == TokenKind::Identifier
+                    && (next.is_punct(Punct::Comma) || next.is_punct(Punct::RightParen))
+            ),
+            TokenKind::Identifier => {
+                let key = tok.text().to_lowercase();
+                self.constants.contains_key(&key) || self.enumerated.contains_key(&key)
+            }
+            _ => false,
+        }
+    }
+
+    fn lookup_type(&self, tok: &Token) -> Result<TypeExpr, LexerError> {
+        let key = tok.text().to_lowercase();
+        match self.types.get(&key) {
+            Some(ty) => Ok(TypeExpr::Named {
+                name: Ident {
+                    name: String::from(tok.text()),
+                    span: tok.span,
+                },
+                ty: Box::new(ty.clone()),
+            }),
+            None if self.defining.as_ref() == Some(&key) => Err(semantic_error(
+                tok,
+                format!("type `{}` can't refer to itself", tok.text()).as_str(),
+            )),
+            None if self.declared.contains(&key) => Err(semantic_error(
+                tok,
+                format!("`{}` is not a type", tok.text()).as_str(),
+            )),
+            None => Err(semantic_error(
+                tok,
+                format!("unknown type `{}`", tok.text()).as_str(),
+            )),
+        }
+    }
+
+    fn parse_array(&mut self, packed: bool) -> Result<TypeExpr, LexerError> {
+        let tok = self.advance("`[`")?;
+        if !tok.is_punct(Punct::LeftBracket) {
+            return Err(syntax_error(
+                tok,
+                format!("expected `[`, found `{}`", tok.text()).as_str(),
+            ));
+        }
+
+        let mut dims = vec![];
+        loop {
+            let start = self.peek().map_or(self.last_end(), |tok| tok.span.start);
+            let index = if self.at_subrange() {
+                self.parse_subrange(true)?
+            } else {
+                self.parse_type()?
+            };
+            let span = Span::new(start, self.last_end());
+            if !index.is_ordinal() {
+                return Err(LexerError::semantic_error(
+                    span.start,
+                    span.len(),
+                    format!("array index should be an ordinal type, found `{}`", index).as_str(),
+                ));
+            }
+            if dims.len() == self.options.max_dimensions {
+                return Err(LexerError::semantic_error(
+                    span.start,
+                    span.len(),
+                    format!(
+                        "array can't have more than {} dimensions",
+                        self.options.max_dimensions
+                    )
+                    .as_str(),
+                ));
+            }
+            dims.push(index);
+
+            let tok = self.advance("`,` or `]`")?;
+            if tok.is_punct(Punct::RightBracket) {
+                break;
+            } else if !tok.is_punct(Punct::Comma) {
+                return Err(syntax_error(
+                    tok,
+                    format!(
+                        "unexpected token: expected `,` or `]`, found `{}`",
+                        tok.text()
+                    )
+                    .as_str(),
+                ));
+            }
+        }
+
+        let tok = self.advance("`of`")?;
+        if !tok.is_keyword(Keyword::Of) {
+            return Err(syntax_error(
+                tok,
+                format!("expected `of`, found `{}`", tok.text()).as_str(),
+            ));
+        }
+
+        let elem = self.parse_type()?;
+
+        Ok(TypeExpr::Array {
+            dims,
+            elem: Box::new(elem),
+            packed,
+        })
+    }
+
+    fn parse_string(&mut self) -> Result<TypeExpr, LexerError> {
+        match self.peek() {
+            Some(tok) if tok.is_punct(Punct::LeftBracket) => self.pos += 1,
+            _ => return Ok(TypeExpr::String(None)),
+        }
+
+        let (len, span) = self.parse_expr("expected string length")?;
+        if !(1..=i32::from(MAX_STRING_LENGTH)).contains(&len) {
+            return Err(LexerError::semantic_error(
+                span.start,
+                span.len(),
+                format!(
+                    "string length should be between 1 and {}, found {}",
+                    MAX_STRING_LENGTH, len
+                )
+                .as_str(),
+            ));
+        }
+        self.expect_punct(Punct::RightBracket)?;
+
+        Ok(TypeExpr::String(Some(len as u8)))
+    }
+
+    fn parse_enum(&mut self, open: &Token) -> Result<TypeExpr, LexerError> {
+        let mut names = vec![];
+        loop {
+            names.push(self.parse_new_ident()?);
+
+            let tok = self.advance("`,` or `)`")?;
+            if tok.is_punct(Punct::RightParen) {
+                break;
+            } else if !tok.is_punct(Punct::Comma) {
+                return Err(syntax_error(
+                    tok,
+                    format!("expected `,` or `)`, found `{}`", tok.text()).as_str(),
+                ));
+            }
+        }
+
+        if names.len() > MAX_ENUM_VALUES {
+            return Err(LexerError::semantic_error(
+                open.span.start,
+                self.last_end() - open.span.start,
+                format!(
+                    "enumerated type can't have more than {} values",
+                    MAX_ENUM_VALUES
+                )
+                .as_str(),
+            ));
+        }
+
+        let ty = TypeExpr::Enum(names.clone());
+        for (ordinal, name) in names.iter().enumerate() {
+            self.enumerated
+                .insert(name.name.to_lowercase(), (ordinal as i32, ty.clone()));
+        }
+
+        Ok(ty)
+    }
+
+    /// Parses `low..high`. The dialect's separator, such as the course `:`,
+    /// is accepted only in an array index.
+    fn parse_subrange(&mut self, index: bool) -> Result<TypeExpr, LexerError> {
+        let (low, low_span, host) = self.parse_bound("expected constant (start of a range)")?;
+
+        let dialect = self.options.dialect;
+        let hint = if index {
+            dialect.range_separator_hint()
+        } else {
+            "`..`"
+        };
+        let tok = self.advance(hint)?;
+        let is_separator = if index {
+            dialect.is_range_separator(tok)
+        } else {
+            tok.is_punct(Punct::DotDot)
+        };
+        if !is_separator {
+            return Err(syntax_error(
+                tok,
+                format!("expected {}, found `{}`", hint, tok.text()).as_str(),
+            ));
+        }
+
+        let (high, high_span, high_host) =
+            self.parse_bound("unexpected token: expected constant")?;
+        if high_host != host {
+            return Err(LexerError::semantic_error(
+                high_span.start,
+                high_span.len(),
+                "bounds of range should be of the same type",
+            ));
+        }
+        if high <= low {
+            return Err(LexerError::semantic_error(
+                high_span.start,
+                high_span.len(),
+                "first bound of range should be less than second",
+            ));
+        }
+        if let Some((min, max)) = host.bounds() {
+            for (value, span) in [(low, low_span), (high, high_span)] {
+                if !(min..=max).contains(&value) {
+                    return Err(LexerError::semantic_error(
+                        span.start,
+                        span.len(),
+                        format!(
+                            "bound of range is out of range of `{}` ({}..{})",
+                            host, min, max
+                        )
+                        .as_str(),
+                    ));
+                }
+            }
+        }
+
+        Ok(TypeExpr::Subrange {
+            range: Range {
+                low,
+                high,
+                span: Span::new(low_span.start, high_span.end),
+            },
+            host: Box::new(host),
+        })
+    }
+
+    /// Parses a subrange bound: an integer expression, a char or an enumerated constant.
+    fn parse_bound(&mut self, expected: &str) -> Result<(i32, Span, TypeExpr), LexerError> {
+        let Some(tok) = self.peek() else {
+            return Err(self.end_of_input("constant"));
+        };
+
+        let char_value = match tok.kind {
+            TokenKind::StringLiteral => tok.string_value(),
+            TokenKind::Identifier => {
+                let key = tok.text().to_lowercase();
+                if let Some((ordinal, ty)) = self.enumerated.get(&key) {
+                    self.pos += 1;
+                    return Ok((*ordinal, tok.span, ty.clone()));
+                }
+
+                match self.constants.get(&key) {
+                    Some(ConstValue::String(value)) => Some(value.clone()),
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+
+        let Some(value) = char_value else {
+            let (value, span) = self.parse_expr(expected)?;
+            return Ok((value, span, TypeExpr::Simple(SimpleType::Integer)));
+        };
+
+        self.pos += 1;
+        let mut chars = value.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok((c as i32, tok.span, TypeExpr::Simple(SimpleType::Char))),
+            _ => Err(semantic_error(
+                tok,
+                format!("`{}` is not an ordinal constant", tok.text()).as_str(),
+            )),
+        }
+    }
+
+    fn parse_set(&mut self) -> Result<TypeExpr, LexerError> {
+        let tok = self.advance("`of`")?;
+        if !tok.is_keyword(Keyword::Of) {
+            return Err(syntax_error(
+                tok,
+                format!("expected `of`, found `{}`", tok.text()).as_str(),
+            ));
+        }
+
+        let start = self.peek().map_or(self.last_end(), |tok| tok.span.start);
+        let base = self.parse_type()?;
+        let span = Span::new(start, self.last_end());
+        match base.bounds() {
+            Some((low, high)) if low >= 0 && high <= MAX_SET_ORDINAL => {}
+            Some((low, high)) => {
+                let message = if low < 0 {
+                    format!(
+                        "set base type can't have negative ordinals, found `{}` with lowest ordinal {}",
+                        base, low
+                    )
+                } else {
+                    format!(
+                        "set base type ordinals can't exceed {}, found `{}` with highest ordinal {}",
+                        MAX_SET_ORDINAL, base, high
+                    )
+                };
+                return Err(LexerError::semantic_error(
+                    span.start,
+                    span.len(),
+                    message.as_str(),
+                ));
+            }
+            None => {
+                return Err(LexerError::semantic_error(
+                    span.start,
+                    span.len(),
+                    format!("set base type should be ordinal, found `{}`", base).as_str(),
+                ))
+            }
+        }
+
+        Ok(TypeExpr::Set(Box::new(base)))
+    }
+
+    fn parse_file(&mut self) -> Result<TypeExpr, LexerError> {
+        match self.peek() {
+            Some(tok) if tok.is_keyword(Keyword::Of) => self.pos += 1,
+            _ => return Ok(TypeExpr::File(None)),
+        }
+
+        let start = self.peek().map_or(self.last_end(), |tok| tok.span.start);
+        let elem = self.parse_type()?;
+        if matches!(
+            elem.resolved(),
+            TypeExpr::File(_) | TypeExpr::Simple(SimpleType::Text)
+        ) {
+            return Err(LexerError::semantic_error(
+                start,
+                self.last_end() - start,
+                format!("file element type can't be a file, found `{}`", elem).as_str(),
+            ));
+        }
+
+        Ok(TypeExpr::File(Some(Box::new(elem))))
+    }
+
+    fn parse_pointer(&mut self) -> Result<TypeExpr, LexerError> {
+        let tok = self.advance("type name")?;
+        match tok.kind {
+            TokenKind::Keyword(keyword) if SimpleType::from_keyword(keyword).is_some() => {}
+            TokenKind::Keyword(Keyword::String) => {}
+            TokenKind::Identifier => {
+                let key = tok.text().to_lowercase();
+                match &mut self.forward {
+                    Some(forward) if !self.types.contains_key(&key) => forward.push(tok),
+                    _ => {
+                        self.lookup_type(tok)?;
+                    }
+                }
+            }
+            _ => {
+                return Err(syntax_error(
+                    tok,
+                    format!("expected type name after `^`, found `{}`", tok.text()).as_str(),
+                ))
+            }
+        }
+
+        Ok(TypeExpr::Pointer(Ident {
+            name: String::from(tok.text()),
+            span: tok.span,
+        }))
+    }
+
+    fn parse_record(&mut self) -> Result<TypeExpr, LexerError> {
+        let mut fields = HashSet::new();
+        let list = self.parse_field_list(&mut fields)?;
+
+        let tok = self.advance("`end`")?;
+        if !tok.is_keyword(Keyword::End) {
+            return Err(syntax_error(
+                tok,
+                format!("expected `end`, found `{}`", tok.text()).as_str(),
+            ));
+        }
+
+        Ok(TypeExpr::Record(list))
+    }
+
+    fn parse_field_list(&mut self, fields: &mut HashSet<String>) -> Result<FieldList, LexerError> {
+        let mut fixed = vec![];
+        let mut variant = None;
+
+        while let Some(tok) = self.peek() {
+            match tok.kind {
+                TokenKind::Identifier => fixed.push(self.parse_field_decl(fields)?),
+                TokenKind::Keyword(Keyword::Case) => {
+                    variant = Some(Box::new(self.parse_variant_part(fields)?));
+                    break;
+                }
+                _ => break,
+            }
+
+            match self.peek() {
+                Some(tok) if tok.is_punct(Punct::Semicolon) => self.pos += 1,
+                _ => break,
+            }
+        }
+
+        Ok(FieldList { fixed, variant })
+    }
+
+    fn parse_field_decl(&mut self, fields: &mut HashSet<String>) -> Result<FieldDecl, LexerError> {
+        let mut names = vec![];
+        loop {
+            let tok = self.advance("field name")?;
+            names.push(declare_field(tok, fields)?);
+
+            let tok = self.advance("`,` or `:`")?;
+            if tok.is_punct(Punct::Colon) {
+                break;
+            } else if !tok.is_punct(Punct::Comma) {
+                return Err(syntax_error(
+                    tok,
+                    format!("`{}` should be either comma or colon", tok.text()).as_str(),
+                ));
+            }
+        }
+
+        let ty = self.parse_type()?;
+
+        Ok(FieldDecl {
+            span: Span::new(names[0].span.start, self.last_end()),
+            names,
+            ty,
+        })
+    }
+
+    fn parse_variant_part(
+        &mut self,
+        fields: &mut HashSet<String>,
+    ) -> Result<VariantPart, LexerError> {
+        let keyword = self.advance("`case`")?;
+
+        let mut tag = None;
+        if let (Some(name), Some(colon)) = (self.peek(), self.tokens.get(self.pos + 1)) {
+            if name.kind == TokenKind::Identifier && colon.is_punct(Punct::Colon) {
+                tag = Some(declare_field(name, fields)?);
+                self.pos += 2;
+            }
+        }
+
+        let Some(tok) = self.peek() else {
+            return Err(self.end_of_input("tag type"));
+        };
+        let tag_type = self.parse_type()?;
+        if !tag_type.is_ordinal() {
+            return Err(semantic_error(
+                tok,
+                format!("tag type should be ordinal, found `{}`", tok.text()).as_str(),
+            ));
+        }
+
+        let tok = self.advance("`of`")?;
+        if !tok.is_keyword(Keyword::Of) {
+            return Err(syntax_error(
+                tok,
+                format!("expected `of`, found `{}`", tok.text()).as_str(),
+            ));
+        }
+
+        let mut labels_seen = HashSet::new();
+        let mut variants = vec![];
+        loop {
+            let start = self.peek().map_or(self.last_end(), |tok| tok.span.start);
+
+            let mut labels = vec![];
+            loop {
+                let (label, span, host) = self.parse_bound("expected case label constant")?;
+                if host != ordinal_host(&tag_type) {
+                    return Err(LexerError::semantic_error(
+                        span.start,
+                        span.len(),
+                        format!("case label should be of tag type `{}`", tag_type).as_str(),
+                    ));
+                }
+                if let Some((low, high)) = tag_type.bounds() {
+                    if !(low..=high).contains(&label) {
+                        return Err(LexerError::semantic_error(
+                            span.start,
+                            span.len(),
+                            format!("case label is out of range of tag type `{}`", tag_type)
+                                .as_str(),
+                        ));
+                    }
+                }
+                if !labels_seen.insert(label) {
+                    return Err(LexerError::semantic_error(
+                        span.start,
+                        span.len(),
+                        "duplicate case label",
+                    ));
+                }
+                labels.push(label);
+
+                let tok = self.advance("`,` or `:`")?;
+                if tok.is_punct(Punct::Colon) {
+                    break;
+                } else if !tok.is_punct(Punct::Comma) {
+                    return Err(syntax_error(
+                        tok,
+                        format!("`{}` should be either comma or colon", tok.text()).as_str(),
+                    ));
+                }
+            }
+
+            self.expect_punct(Punct::LeftParen)?;
+            let list = self.parse_field_list(fields)?;
+            self.expect_punct(Punct::RightParen)?;
+
+            variants.push(Variant {
+                labels,
+                fields: list,
+                span: Span::new(start, self.last_end()),
+            });
+
+            match self.peek() {
+                Some(tok) if tok.is_punct(Punct::Semicolon) => self.pos += 1,
+                _ => break,
+            }
+            match self.peek() {
+                Some(tok) if tok.is_keyword(Keyword::End) || tok.is_punct(Punct::RightParen) => {
+                    break
+                }
+                _ => {}
+            }
+        }
+
+        Ok(VariantPart {
+            tag,
+            tag_type,
+            variants,
+            span: Span::new(keyword.span.start, self.last_end()),
+        })
+    }
+
+    fn parse_expr(&mut self, expected: &str) -> Result<(i32, Span), LexerError> {
+        let mut sign = None;
+        if let Some(tok) = self.peek() {
+            if tok.is_punct(Punct::Minus) || tok.is_punct(Punct::Plus) {
+                sign = Some(tok);
+                self.pos += 1;
+            }
+        }
+
+        let (mut value, mut span) = match (sign, self.peek()) {
+            (Some(sign), Some(tok)) if matches!(tok.kind, TokenKind::IntegerLiteral(_)) => {
+                // A signed literal is checked as a whole, so that `-32768` fits.
+                self.pos += 1;
+                let TokenKind::IntegerLiteral(literal) = tok.kind else {
+                    unreachable!()
+                };
+                let (value, span) = integer_constant(Some(sign), tok, literal)?;
+                self.parse_term_rest(expected, value.into(), span)?
+            }
+            (Some(sign), _) => {
+                let (value, span) = self.parse_term(expected)?;
+                let span = Span::new(sign.span.start, span.end);
+                let value = if sign.is_punct(Punct::Minus) {
+                    -value
+                } else {
+                    value
+                };
+                (checked_integer(value, span)?, span)
+            }
+            (None, _) => self.parse_term(expected)?,
+        };
+
+        while let Some(op) = self.peek() {
+            if !op.is_punct(Punct::Plus) && !op.is_punct(Punct::Minus) {
+                break;
+            }
+            self.pos += 1;
+
+            let (rhs, rhs_span) = self.parse_term(expected)?;
+            span = Span::new(span.start, rhs_span.end);
+            value = if op.is_punct(Punct::Plus) {
+                checked_integer(value + rhs, span)?
+            } else {
+                checked_integer(value - rhs, span)?
+            };
+        }
+
+        Ok((value as i32, span))
+    }
+
+    fn parse_term(&mut self, expected: &str) -> Result<(i64, Span), LexerError> {
+        let (value, span) = self.parse_factor(expected)?;
+        self.parse_term_rest(expected, value, span)
+    }
+
+    fn parse_term_rest(
+        &mut self,
+        expected: &str,
+        mut value: i64,
+        mut span: Span,
+    ) -> Result<(i64, Span), LexerError> {
+        while let Some(op) = self.peek() {
+            if !op.is_punct(Punct::Star)
+                && !op.is_keyword(Keyword::Div)
+                && !op.is_keyword(Keyword::Mod)
+            {
+                break;
+            }
+            self.pos += 1;
+
+            let (rhs, rhs_span) = self.parse_factor(expected)?;
+            if rhs == 0 && !op.is_punct(Punct::Star) {
+                return Err(LexerError::semantic_error(
+                    rhs_span.start,
+                    rhs_span.len(),
+                    "division by zero in constant expression",
+                ));
+            }
+
+            span = Span::new(span.start, rhs_span.end);
+            value = if op.is_punct(Punct::Star) {
+                checked_integer(value * rhs, span)?
+            } else if op.is_keyword(Keyword::Div) {
+                checked_integer(value / rhs, span)?
+            } else {
+                checked_integer(value % rhs, span)?
+            };
+        }
+
+        Ok((value, span))
+    }
+
+    fn parse_factor(&mut self, expected: &str) -> Result<(i64, Span), LexerError> {
+        let tok = self.advance("integer constant")?;
+        let value = match tok.kind {
+            TokenKind::IntegerLiteral(value) => value,
+            TokenKind::Identifier if self.enumerated.contains_key(&tok.text().to_lowercase()) => {
+                self.enumerated[&tok.text().to_lowercase()].0
+            }
+            TokenKind::Identifier => match self.lookup_constant(tok)? {
+                ConstValue::Integer(value) => *value,
+                _ => {
+                    return Err(semantic_error(
+                        tok,
+                        format!("`{}` is not an ordinal constant", tok.text()).as_str(),
+                    ))
+                }
+            },
+            TokenKind::Punct(Punct::LeftParen) => {
+                let (value, _) = self.parse_expr(expected)?;
+                self.expect_punct(Punct::RightParen)?;
+
+                return Ok((value.into(), Span::new(tok.span.start, self.last_end())));
+            }
+            _ => {
+                return Err(syntax_error(
+                    tok,
+                    format!("{}, found `{}`", expected, tok.text()).as_str(),
+                ))
+            }
+        };
+
+        Ok((checked_integer(value.into(), tok.span)?, tok.span))
+    }
+}
+
+fn ident(tok: &Token) -> Result<Ident, LexerError> {
+    match tok.kind {
+        TokenKind::Identifier => {
+            if tok.text().chars().count() > MAX_IDENTIFIER_LENGTH {
+                return Err(semantic_error(
+                    tok,
+                    "identifier can't be longer than 8 characters",
+                ));
+            }
+
+            Ok(Ident {
+                name: String::from(tok.text()),
+                span: tok.span,
+            })
+        }
+        TokenKind::Keyword(_) => Err(semantic_error(
+            tok,
+            "identifier can't reserved word: var, real, double, etc.",
+        )),
+        _ => match tok
+            .text()
+            .chars()
+            .find(|ch| ch.is_alphabetic() && !ch.is_ascii())
+        {
+            Some(letter) => Err(syntax_error(
+                tok,
+                format!(
+                    "identifier can only contain ASCII letters, digits and `_`, found `{}` in `{}`",
+                    letter,
+                    tok.text()
+                )
+                .as_str(),
+            )),
+            None => Err(syntax_error(
+                tok,
+                format!("`{}` should be a valid identifier", tok.text()).as_str(),
+            )),
+        },
+    }
+}
+
+fn declare_field(tok: &Token, fields: &mut HashSet<String>) -> Result<Ident, LexerError> {
+    let ident = ident(tok)?;
+    if !fields.insert(ident.name.to_lowercase()) {
+        return Err(semantic_error(
+            tok,
+            format!("duplicate field `{}`", tok.text()).as_str(),
+        ));
+    }
+
+    Ok(ident)
+}
+
+/// Type the values of an ordinal type are written in: integer literals for
+/// the integer types, chars, or the members of a boolean or enumerated type.
+fn ordinal_host(ty: &TypeExpr) -> TypeExpr {
+    match ty.resolved() {
+        TypeExpr::Subrange { host, .. } => ordinal_host(host),
+        TypeExpr::Simple(SimpleType::Char | SimpleType::Boolean) | TypeExpr::Enum(_) => {
+            ty.resolved().clone()
+        }
+        _ => TypeExpr::Simple(SimpleType::Integer),
+    }
+}
+
+fn syntax_error(tok: &Token, message: &str) -> LexerError {
+    LexerError::syntax_error(tok.span.start, tok.span.len(), message)
+}
+
+fn semantic_error(tok: &Token, message: &str) -> LexerError {
+    LexerError::semantic_error(tok.span.start, tok.span.len(), message)
+}
+
+fn integer_constant(
+    sign: Option<&Token>,
+    tok: &Token,
+    value: i32,
+) -> Result<(i32, Span), LexerError> {
+    let (value, span) = match sign {
+        Some(sign) if sign.is_punct(Punct::Minus) => {
+            (-value, Span::new(sign.span.start, tok.span.end))
+        }
+        Some(sign) => (value, Span::new(sign.span.start, tok.span.end)),
+        None => (value, tok.span),
+    };
+
+    checked_integer(value.into(), span)?;
+
+    Ok((value, span))
+}
+
+fn checked_integer(value: i64, span: Span) -> Result<i64, LexerError> {
+    if !is_integer_in_range(value) {
+        return Err(LexerError::integer_out_of_range(
+            span.start,
+            span.len(),
+            &value.to_string(),
+        ));
+    }
+
+    Ok(value)
+}
+
+fn is_integer_in_range(value: i64) -> bool {
+    (-32768..=32767).contains(&value)
+}
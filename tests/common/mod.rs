@@ -0,0 +1,27 @@
+#![allow(dead_code)]
+
+use analyzer::ast::{DeclarationPart, TypeExpr};
+use analyzer::{analyze, tokenize, LexerError};
+
+pub fn run(source: &str) -> Result<DeclarationPart, LexerError> {
+    analyze(tokenize(String::from(source))?)
+}
+
+/// Types of the declared variables, in declaration order.
+pub fn types(source: &str) -> Vec<TypeExpr> {
+    run(source)
+        .unwrap()
+        .var_decls()
+        .map(|decl| decl.ty.clone())
+        .collect()
+}
+
+/// Type of the last declared variable.
+pub fn type_of(source: &str) -> TypeExpr {
+    types(source).pop().unwrap()
+}
+
+/// Displayed types of the declared variables, in declaration order.
+pub fn type_strings(source: &str) -> Vec<String> {
+    types(source).iter().map(|ty| ty.to_string()).collect()
+}
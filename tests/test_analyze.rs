@@ -0,0 +1,527 @@
+mod common;
+
+use analyzer::ast::{ConstValue, Section, SimpleType, TypeExpr, VarSection};
+use analyzer::{analyze_with, tokenize, Dialect, ErrorKind, LexerError, Options, Span};
+
+fn run(source: &str) -> Result<VarSection, LexerError> {
+    Ok(common::run(source)?.var_sections().last().unwrap().clone())
+}
+
+fn run_with(source: &str, options: Options) -> Result<VarSection, LexerError> {
+    let part = analyze_with(tokenize(String::from(source))?, &options)?;
+    Ok(part.var_sections().last().unwrap().clone())
+}
+
+fn dialect(dialect: Dialect) -> Options {
+    Options {
+        dialect,
+        ..Options::default()
+    }
+}
+
+fn type_of(section: &VarSection, name: &str) -> String {
+    section
+        .decls
+        .iter()
+        .find(|decl| decl.names.iter().any(|ident| ident.name == name))
+        .map(|decl| decl.ty.to_string())
+        .unwrap()
+}
+
+#[test]
+fn test_simple() {
+    let section = run("var a, b: byte;").unwrap();
+
+    assert_eq!(type_of(&section, "a"), "byte");
+    assert_eq!(type_of(&section, "b"), "byte");
+}
+
+#[test]
+fn test_array() {
+    let section = run("VAR A,K:ARRAY[2:10,10:40] OF BYTE, D17,E7 : WORD;").unwrap();
+
+    assert_eq!(type_of(&section, "A"), "array[2..10,10..40] of byte");
+    assert_eq!(type_of(&section, "E7"), "word");
+}
+
+#[test]
+fn test_missing_var() {
+    let err = run("a: byte;").unwrap_err();
+
+    assert_eq!(err.pos(), 0);
+    assert_eq!(err.tok_length(), 1);
+}
+
+#[test]
+fn test_keyword_as_identifier() {
+    let err = run("var a, byte: word;").unwrap_err();
+
+    assert_eq!(err.pos(), 7);
+    assert_eq!(err.tok_length(), 4);
+}
+
+#[test]
+fn test_unexpected_punct() {
+    let err = run("var a; byte;").unwrap_err();
+
+    assert_eq!(err.pos(), 5);
+}
+
+#[test]
+fn test_range_order() {
+    let err = run("var a: array[10:2] of byte;").unwrap_err();
+
+    assert_eq!(err.pos(), 16);
+}
+
+#[test]
+fn test_missing_semicolon() {
+    let err = run("var a: byte").unwrap_err();
+
+    assert_eq!(err.pos(), 11);
+}
+
+#[test]
+fn test_standard_range_separator() {
+    let section = run("var a: array[1..10, 2..5] of real;").unwrap();
+
+    assert_eq!(type_of(&section, "a"), "array[1..10,2..5] of real");
+}
+
+#[test]
+fn test_mixed_range_separators() {
+    let section = run("var a: array[1..10, 2:5] of real;").unwrap();
+
+    assert_eq!(type_of(&section, "a"), "array[1..10,2..5] of real");
+}
+
+#[test]
+fn test_separator_spelling_is_normalised() {
+    let course = run_with("var a: array[1:10] of char;", dialect(Dialect::Course)).unwrap();
+    let standard = run_with("var a: array[1..10] of char;", dialect(Dialect::Standard)).unwrap();
+
+    assert_eq!(
+        course.decls[0].ty.to_string(),
+        standard.decls[0].ty.to_string()
+    );
+}
+
+#[test]
+fn test_course_dialect_rejects_dot_dot() {
+    let err = run_with("var a: array[1..10] of char;", dialect(Dialect::Course)).unwrap_err();
+
+    assert_eq!(err.pos(), 14);
+    assert_eq!(err.tok_length(), 2);
+}
+
+#[test]
+fn test_colon_only_separates_array_indices() {
+    let err = run("var d: 1:31;").unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Syntax);
+    assert_eq!(err.pos(), 8);
+    assert_eq!(err.tok_length(), 1);
+
+    assert!(common::run("type t = 1:5;").is_err());
+    assert!(run("var s: set of 0:255;").is_err());
+    assert!(run("var r: record case t: 1:3 of 1: (a: byte) end;").is_err());
+    assert!(run_with("var d: 1..31;", dialect(Dialect::Course)).is_ok());
+}
+
+#[test]
+fn test_standard_dialect_rejects_colon() {
+    let err = run_with("var a: array[1:10] of char;", dialect(Dialect::Standard)).unwrap_err();
+
+    assert_eq!(err.pos(), 14);
+    assert_eq!(err.tok_length(), 1);
+}
+
+#[test]
+fn test_negative_bounds() {
+    let section = run("var a: array[-5:5] of byte;").unwrap();
+
+    assert_eq!(type_of(&section, "a"), "array[-5..5] of byte");
+}
+
+#[test]
+fn test_explicitly_positive_bounds() {
+    let section = run("var a: array[+3..+7, -10..-2] of byte;").unwrap();
+
+    assert_eq!(type_of(&section, "a"), "array[3..7,-10..-2] of byte");
+}
+
+#[test]
+fn test_integer_boundaries() {
+    let section = run("var a: array[-32768..32767] of byte;").unwrap();
+
+    assert_eq!(type_of(&section, "a"), "array[-32768..32767] of byte");
+}
+
+#[test]
+fn test_below_lower_boundary() {
+    let err = run("var a: array[-32769..0] of byte;").unwrap_err();
+
+    assert_eq!(err.pos(), 13);
+    assert_eq!(err.tok_length(), 6);
+}
+
+#[test]
+fn test_above_upper_boundary() {
+    let err = run("var a: array[0..32768] of byte;").unwrap_err();
+
+    assert_eq!(err.pos(), 16);
+    assert_eq!(err.tok_length(), 5);
+}
+
+#[test]
+fn test_negative_range_order() {
+    let err = run("var a: array[-5..-10] of byte;").unwrap_err();
+
+    assert_eq!(err.pos(), 17);
+    assert_eq!(err.tok_length(), 3);
+}
+
+#[test]
+fn test_double_sign() {
+    let err = run("var a: array[--5..5] of byte;").unwrap_err();
+
+    assert_eq!(err.pos(), 14);
+}
+
+#[test]
+fn test_three_dimensions() {
+    let section = run("var a: array[1:2, 3:4, 5:6] of byte;").unwrap();
+
+    assert_eq!(type_of(&section, "a"), "array[1..2,3..4,5..6] of byte");
+}
+
+#[test]
+fn test_max_dimensions() {
+    let options = Options {
+        max_dimensions: 2,
+        ..Options::default()
+    };
+
+    assert!(run_with("var a: array[1:2, 3:4] of byte;", options.clone()).is_ok());
+
+    let err = run_with("var a: array[1:2, 3:4, 5:6] of byte;", options).unwrap_err();
+
+    assert_eq!(err.pos(), 23);
+    assert_eq!(err.tok_length(), 3);
+}
+
+#[test]
+fn test_missing_closing_bracket() {
+    let err = run("var a: array[1:2, 3:4 of byte;").unwrap_err();
+
+    assert_eq!(err.pos(), 22);
+}
+
+#[test]
+fn test_ast_bounds() {
+    let section = run("var m: array[-2..3, 1..4] of real;").unwrap();
+
+    match &section.decls[0].ty {
+        TypeExpr::Array { dims, elem, packed } => {
+            assert_eq!(dims.len(), 2);
+            assert_eq!(dims[0].bounds(), Some((-2, 3)));
+            assert_eq!(dims[1].bounds(), Some((1, 4)));
+            match &dims[0] {
+                TypeExpr::Subrange { range, .. } => assert_eq!(range.span, Span::new(13, 18)),
+                ty => panic!("expected subrange, found {:?}", ty),
+            }
+            assert_eq!(**elem, TypeExpr::Simple(SimpleType::Real));
+            assert!(!packed);
+        }
+        ty => panic!("expected array, found {:?}", ty),
+    }
+}
+
+#[test]
+fn test_ast_decls() {
+    let section = run("var a, b: byte, c: word;").unwrap();
+
+    assert_eq!(section.decls.len(), 2);
+    assert_eq!(section.decls[0].names[0].name, "a");
+    assert_eq!(section.decls[0].names[1].span, Span::new(7, 8));
+    assert_eq!(section.decls[0].span, Span::new(4, 14));
+    assert_eq!(section.decls[1].ty, TypeExpr::Simple(SimpleType::Word));
+    assert_eq!(section.span, Span::new(0, 24));
+}
+
+#[test]
+fn test_duplicate_is_case_insensitive() {
+    let err = run("var abc, ABC: byte;").unwrap_err();
+
+    assert_eq!(err.pos(), 9);
+}
+
+#[test]
+fn test_identifiers_keep_source_order() {
+    let section = run("var zeta, alpha, mid: byte, b2, a1: word, q: char;").unwrap();
+
+    let names: Vec<&str> = section
+        .identifiers()
+        .map(|(ident, _)| ident.name.as_str())
+        .collect();
+
+    assert_eq!(names, vec!["zeta", "alpha", "mid", "b2", "a1", "q"]);
+}
+
+#[test]
+fn test_identifiers_keep_grouping() {
+    let section = run("VAR A,K:ARRAY[2:10,10:40] OF BYTE, D17,E7 : WORD;").unwrap();
+
+    let groups: Vec<Vec<&str>> = section
+        .decls
+        .iter()
+        .map(|decl| decl.names.iter().map(|ident| ident.name.as_str()).collect())
+        .collect();
+
+    assert_eq!(groups, vec![vec!["A", "K"], vec!["D17", "E7"]]);
+}
+
+#[test]
+fn test_identifiers_report_types() {
+    let section = run("var b, a: array[1..2] of byte, c: word;").unwrap();
+
+    let report: Vec<String> = section
+        .identifiers()
+        .map(|(ident, ty)| format!("{}: {}", ident.name, ty))
+        .collect();
+
+    assert_eq!(
+        report,
+        vec![
+            "b: array[1..2] of byte",
+            "a: array[1..2] of byte",
+            "c: word"
+        ]
+    );
+}
+
+#[test]
+fn test_report() {
+    let part = common::run("var a, b: byte, c: array[0..3] of char;").unwrap();
+
+    assert_eq!(
+        part.report(),
+        "Identifier: a, type: byte\n\
+         Identifier: b, type: byte\n\
+         Identifier: c, type: array[0..3] of char\n"
+    );
+}
+
+#[test]
+fn test_multiple_declarations() {
+    let section = run("var a: byte; b: word; c, d: array[1..2] of char;").unwrap();
+
+    assert_eq!(section.decls.len(), 3);
+    assert_eq!(type_of(&section, "a"), "byte");
+    assert_eq!(type_of(&section, "b"), "word");
+    assert_eq!(type_of(&section, "d"), "array[1..2] of char");
+}
+
+#[test]
+fn test_mixed_declaration_separators() {
+    let section = run("var a: byte, b: word; c: real;").unwrap();
+
+    assert_eq!(section.decls.len(), 3);
+    assert_eq!(type_of(&section, "c"), "real");
+}
+
+#[test]
+fn test_duplicate_across_declarations() {
+    let err = run("var a: byte; A: word;").unwrap_err();
+
+    assert_eq!(err.pos(), 13);
+}
+
+#[test]
+fn test_trailing_garbage() {
+    let err = run("var a: byte; b: word; 42").unwrap_err();
+
+    assert_eq!(err.pos(), 22);
+    assert_eq!(err.tok_length(), 2);
+}
+
+#[test]
+fn test_unterminated_second_declaration() {
+    let err = run("var a: byte; b: word").unwrap_err();
+
+    assert_eq!(err.pos(), 20);
+}
+
+#[test]
+fn test_sections() {
+    let part = common::run(
+        "label 10, done; const n = 5; m = -2; type t = array[1..3] of real; var a: word; b: byte;",
+    )
+    .unwrap();
+
+    assert_eq!(part.sections.len(), 4);
+    match &part.sections[0] {
+        Section::Label(labels) => {
+            let names: Vec<&str> = labels.labels.iter().map(|l| l.name.as_str()).collect();
+            assert_eq!(names, vec!["10", "done"]);
+        }
+        section => panic!("expected label section, found {:?}", section),
+    }
+    match &part.sections[1] {
+        Section::Const(consts) => {
+            assert_eq!(consts.decls[0].name.name, "n");
+            assert_eq!(consts.decls[0].value, ConstValue::Integer(5));
+            assert_eq!(consts.decls[1].value, ConstValue::Integer(-2));
+        }
+        section => panic!("expected const section, found {:?}", section),
+    }
+    match &part.sections[2] {
+        Section::Type(types) => {
+            assert_eq!(types.decls[0].name.name, "t");
+            assert_eq!(types.decls[0].ty.to_string(), "array[1..3] of real");
+        }
+        section => panic!("expected type section, found {:?}", section),
+    }
+}
+
+#[test]
+fn test_multiple_var_sections() {
+    let part = common::run("var a: byte; const n = 1; var b: word; var c: char;").unwrap();
+
+    assert_eq!(part.var_sections().count(), 3);
+
+    let names: Vec<&str> = part
+        .identifiers()
+        .map(|(ident, _)| ident.name.as_str())
+        .collect();
+    assert_eq!(names, vec!["a", "b", "c"]);
+}
+
+#[test]
+fn test_duplicate_across_sections() {
+    let err = common::run("const a = 1; var a: byte;").unwrap_err();
+
+    assert_eq!(err.pos(), 17);
+}
+
+#[test]
+fn test_unknown_section() {
+    let err = common::run("begin").unwrap_err();
+
+    assert_eq!(err.pos(), 0);
+    assert_eq!(err.tok_length(), 5);
+}
+
+#[test]
+fn test_empty_input() {
+    let err = common::run("").unwrap_err();
+
+    assert_eq!(err.pos(), 0);
+    assert_eq!(err.tok_length(), 0);
+}
+
+#[test]
+fn test_label_out_of_range() {
+    let err = common::run("label 10000;").unwrap_err();
+
+    assert_eq!(err.pos(), 6);
+    assert_eq!(err.tok_length(), 5);
+}
+
+#[test]
+fn test_duplicate_label() {
+    let err = common::run("label 7, 007;").unwrap_err();
+
+    assert_eq!(err.pos(), 9);
+}
+
+#[test]
+fn test_const_requires_equal() {
+    let err = common::run("const n: 5;").unwrap_err();
+
+    assert_eq!(err.pos(), 7);
+}
+
+#[test]
+fn test_const_values() {
+    let part =
+        common::run("const n = 10; m = -n; pi = 2.75; neg = -2.5; s = 'it''s'; var a: byte;")
+            .unwrap();
+
+    let values: Vec<ConstValue> = match &part.sections[0] {
+        Section::Const(consts) => consts.decls.iter().map(|d| d.value.clone()).collect(),
+        section => panic!("expected const section, found {:?}", section),
+    };
+
+    assert_eq!(
+        values,
+        vec![
+            ConstValue::Integer(10),
+            ConstValue::Integer(-10),
+            ConstValue::Real(2.75),
+            ConstValue::Real(-2.5),
+            ConstValue::String(String::from("it's")),
+        ]
+    );
+}
+
+#[test]
+fn test_constant_bounds() {
+    let section = run("const n = 10; lo = -3; var a: array[1..n, -lo..n] of byte;").unwrap();
+
+    assert_eq!(type_of(&section, "a"), "array[1..10,3..10] of byte");
+}
+
+#[test]
+fn test_unknown_constant() {
+    let err = run("var a: array[1..n] of byte;").unwrap_err();
+
+    assert_eq!(err.kind(), ErrorKind::Semantic);
+    assert_eq!(err.pos(), 16);
+    assert_eq!(err.tok_length(), 1);
+}
+
+#[test]
+fn test_not_an_ordinal_constant() {
+    let err = run("const pi = 2.75; var a: array[1..pi] of byte;").unwrap_err();
+
+    assert_eq!(err.kind(), ErrorKind::Semantic);
+    assert_eq!(err.pos(), 33);
+    assert_eq!(err.tok_length(), 2);
+}
+
+#[test]
+fn test_string_constant_bound() {
+    let err = run("const s = 'abc'; var a: array[s..5] of byte;").unwrap_err();
+
+    assert_eq!(err.pos(), 30);
+}
+
+#[test]
+fn test_variable_as_bound() {
+    let err = run("var n: byte; a: array[1..n] of byte;").unwrap_err();
+
+    assert_eq!(err.pos(), 25);
+}
+
+#[test]
+fn test_constant_bounds_order() {
+    let err = run("const n = 10; var a: array[n..5] of byte;").unwrap_err();
+
+    assert_eq!(err.pos(), 30);
+}
+
+#[test]
+fn test_multiline_declarations() {
+    let part =
+        common::run("const\r\n\tn = 10;\r\nvar\r\n\ta, b: array[1..n] of byte;\r\n\tc: real;\r\n")
+            .unwrap();
+
+    assert_eq!(part.var_decls().count(), 2);
+}
+
+#[test]
+fn test_multiline_error_position() {
+    let err = run("var\r\n\ta: byte;\r\n\ta: word;").unwrap_err();
+
+    assert_eq!(err.pos(), 17);
+}
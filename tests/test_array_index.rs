@@ -0,0 +1,105 @@
+mod common;
+
+use analyzer::ast::{SimpleType, TypeExpr};
+use analyzer::ErrorKind;
+use common::{run, type_of};
+
+fn dims(source: &str) -> Vec<TypeExpr> {
+    match type_of(source).resolved() {
+        TypeExpr::Array { dims, .. } => dims.clone(),
+        ty => panic!("expected array, found {:?}", ty),
+    }
+}
+
+#[test]
+fn test_char_index() {
+    let dims = dims("var counts: array[char] of byte;");
+
+    assert_eq!(dims, vec![TypeExpr::Simple(SimpleType::Char)]);
+    assert_eq!(dims[0].bounds(), Some((0, 255)));
+}
+
+#[test]
+fn test_boolean_index() {
+    let dims = dims("var b: array[boolean] of real;");
+
+    assert_eq!(dims[0].bounds(), Some((0, 1)));
+}
+
+#[test]
+fn test_char_subrange_index() {
+    let dims = dims("var letters: array['a'..'z'] of integer;");
+
+    assert_eq!(dims[0].bounds(), Some((97, 122)));
+    assert_eq!(dims[0].to_string(), "'a'..'z'");
+}
+
+#[test]
+fn test_enum_index() {
+    let dims = dims("type color = (red, green, blue); var c: array[color] of real;");
+
+    assert_eq!(dims[0].bounds(), Some((0, 2)));
+    assert_eq!(dims[0].describe(), "color ((red, green, blue))");
+}
+
+#[test]
+fn test_inline_enum_and_subrange_index() {
+    let dims =
+        dims("type color = (red, green, blue); var a: array[(lo, hi), green..blue] of byte;");
+
+    assert_eq!(dims[0].bounds(), Some((0, 1)));
+    assert_eq!(dims[1].bounds(), Some((1, 2)));
+}
+
+#[test]
+fn test_mixed_indices_display() {
+    let part = run("type color = (red, green); var a: array[color, 1..3, char] of byte;").unwrap();
+
+    assert_eq!(
+        part.var_decls().last().unwrap().ty.to_string(),
+        "array[color,1..3,char] of byte"
+    );
+}
+
+#[test]
+fn test_real_index() {
+    let err = run("var a: array[real] of byte;").unwrap_err();
+
+    assert_eq!(err.kind(), ErrorKind::Semantic);
+    assert_eq!(err.pos(), 13);
+    assert_eq!(err.tok_length(), 4);
+}
+
+#[test]
+fn test_non_ordinal_named_index() {
+    let err = run("type name = string[10]; var a: array[1..2, name] of byte;").unwrap_err();
+
+    assert_eq!(err.kind(), ErrorKind::Semantic);
+    assert_eq!(err.pos(), 43);
+    assert_eq!(err.tok_length(), 4);
+}
+
+#[test]
+fn test_char_index_out_of_char_range() {
+    let err = run("var a: array['а'..'я'] of byte;").unwrap_err();
+
+    assert_eq!(err.kind(), ErrorKind::Semantic);
+    assert_eq!(err.pos(), 13);
+    assert_eq!(err.tok_length(), 4);
+}
+
+#[test]
+fn test_parenthesised_lower_bound() {
+    let dims = dims("var a: array[(1+2)*3..20] of byte;");
+
+    assert_eq!(dims[0].bounds(), Some((9, 20)));
+}
+
+#[test]
+fn test_parenthesised_subrange() {
+    assert_eq!(type_of("var a: (1+2)*3..20;").bounds(), Some((9, 20)));
+
+    let ty = type_of("const n = 4; var d: (n-1)*2..(n+1)*3;");
+
+    assert_eq!(ty.to_string(), "6..15");
+}
@@ -0,0 +1,64 @@
+mod common;
+
+use analyzer::ast::{SimpleType, TypeCategory, TypeExpr};
+use analyzer::ErrorKind;
+use common::{run, type_of, types};
+
+#[test]
+fn test_all_builtin_types() {
+    let names: Vec<String> = types(
+        "var a: shortint; b: longint; c: boolean; d: single; \
+         e: extended; f: comp; g: pointer; h: text;",
+    )
+    .iter()
+    .map(|ty| ty.to_string())
+    .collect();
+
+    assert_eq!(
+        names,
+        vec!["shortint", "longint", "boolean", "single", "extended", "comp", "pointer", "text"]
+    );
+}
+
+#[test]
+fn test_categories() {
+    assert_eq!(SimpleType::ShortInt.category(), TypeCategory::Ordinal);
+    assert_eq!(SimpleType::Boolean.category(), TypeCategory::Ordinal);
+    assert_eq!(SimpleType::Char.category(), TypeCategory::Ordinal);
+    assert_eq!(SimpleType::Comp.category(), TypeCategory::Real);
+    assert_eq!(SimpleType::Extended.category(), TypeCategory::Real);
+    assert_eq!(SimpleType::Pointer.category(), TypeCategory::Other);
+    assert_eq!(SimpleType::Text.category(), TypeCategory::Other);
+}
+
+#[test]
+fn test_named_type_category() {
+    let types = types("type flag = boolean; var f: flag; s: string;");
+
+    assert!(types[0].is_ordinal());
+    assert_eq!(types[1].category(), TypeCategory::Other);
+}
+
+#[test]
+fn test_case_insensitive() {
+    assert_eq!(
+        type_of("var n: LongInt;"),
+        TypeExpr::Simple(SimpleType::LongInt)
+    );
+}
+
+#[test]
+fn test_builtin_type_is_reserved() {
+    let err = run("var boolean: byte;").unwrap_err();
+
+    assert_eq!(err.kind(), ErrorKind::Semantic);
+    assert_eq!(err.pos(), 4);
+}
+
+#[test]
+fn test_real_tag_type_rejected() {
+    let err = run("var s: record case comp of 0: (a: byte) end;").unwrap_err();
+
+    assert_eq!(err.kind(), ErrorKind::Semantic);
+    assert_eq!(err.pos(), 19);
+}
@@ -0,0 +1,86 @@
+use analyzer::{analyze, tokenize, tokenize_with_trivia, ErrorKind, Span, TokenKind};
+
+fn texts(source: &str) -> Vec<String> {
+    tokenize(String::from(source))
+        .unwrap()
+        .iter()
+        .map(|tok| String::from(tok.text()))
+        .collect()
+}
+
+#[test]
+fn test_brace_comment() {
+    assert_eq!(
+        texts("var { counter } i: byte;"),
+        vec!["var", "i", ":", "byte", ";"]
+    );
+}
+
+#[test]
+fn test_paren_star_comment() {
+    assert_eq!(
+        texts("var (* a\n multi-line { nested-looking } comment *) i: byte;"),
+        vec!["var", "i", ":", "byte", ";"]
+    );
+}
+
+#[test]
+fn test_line_comment() {
+    assert_eq!(
+        texts("var i: byte; // loop counter\r\n    j: word; // last"),
+        vec!["var", "i", ":", "byte", ";", "j", ":", "word", ";"]
+    );
+}
+
+#[test]
+fn test_comment_delimiters_in_string() {
+    let tokens = tokenize(String::from("s = '{ (* // *) }';")).unwrap();
+
+    assert_eq!(tokens[2].string_value().unwrap(), "{ (* // *) }");
+}
+
+#[test]
+fn test_comment_without_spaces() {
+    assert_eq!(texts("a{x}:(*y*)byte"), vec!["a", ":", "byte"]);
+}
+
+#[test]
+fn test_unterminated_brace_comment() {
+    let err = tokenize(String::from("var i: byte; { never closed")).unwrap_err();
+
+    assert_eq!(err.kind(), ErrorKind::Lexical);
+    assert_eq!(err.span(), Span::new(13, 14));
+    assert!(err.to_string().contains("unterminated comment"));
+}
+
+#[test]
+fn test_unterminated_paren_star_comment() {
+    let err = tokenize(String::from("var (* i: byte; }")).unwrap_err();
+
+    assert_eq!(err.kind(), ErrorKind::Lexical);
+    assert_eq!(err.span(), Span::new(4, 6));
+
+    let err = tokenize(String::from("var (*)")).unwrap_err();
+
+    assert_eq!(err.span(), Span::new(4, 6));
+}
+
+#[test]
+fn test_trivia_tokens() {
+    let source = "var { a } i: byte; // end";
+    let tokens = tokenize_with_trivia(String::from(source)).unwrap();
+
+    assert_eq!(tokens[1].kind(), TokenKind::Comment);
+    assert_eq!(tokens[1].text(), "{ a }");
+    assert_eq!(tokens[1].span(), Span::new(4, 9));
+    assert_eq!(tokens.last().unwrap().kind(), TokenKind::Comment);
+    assert_eq!(tokens.last().unwrap().text(), "// end");
+}
+
+#[test]
+fn test_analyze_skips_trivia() {
+    let tokens = tokenize_with_trivia(String::from("var (* x *) i: { y } byte;")).unwrap();
+    let part = analyze(tokens).unwrap();
+
+    assert_eq!(part.var_decls().count(), 1);
+}
@@ -0,0 +1,113 @@
+mod common;
+
+use analyzer::ast::TypeExpr;
+use analyzer::{ErrorKind, LexerError};
+use common::run;
+
+fn bounds(source: &str) -> Result<Vec<(i32, i32)>, LexerError> {
+    let part = run(source)?;
+
+    match &part.var_decls().last().unwrap().ty {
+        TypeExpr::Array { dims, .. } => {
+            Ok(dims.iter().map(|index| index.bounds().unwrap()).collect())
+        }
+        ty => panic!("expected array, found {:?}", ty),
+    }
+}
+
+#[test]
+fn test_arithmetic() {
+    assert_eq!(
+        bounds("const n = 10; max = 4; var a: array[0..n-1, 1..2*max] of byte;").unwrap(),
+        vec![(0, 9), (1, 8)]
+    );
+}
+
+#[test]
+fn test_precedence() {
+    assert_eq!(
+        bounds("var a: array[1..2+3*4] of byte;").unwrap(),
+        vec![(1, 14)]
+    );
+}
+
+#[test]
+fn test_parentheses() {
+    assert_eq!(
+        bounds("var a: array[1..(2+3)*4] of byte;").unwrap(),
+        vec![(1, 20)]
+    );
+}
+
+#[test]
+fn test_div_mod() {
+    assert_eq!(
+        bounds("var a: array[17 mod 5..17 div 5, -7 div 2..-7 mod 3] of byte;").unwrap(),
+        vec![(2, 3), (-3, -1)]
+    );
+}
+
+#[test]
+fn test_unary_minus() {
+    assert_eq!(
+        bounds("const n = 3; var a: array[-n*2..-(n-5)] of byte;").unwrap(),
+        vec![(-6, 2)]
+    );
+}
+
+#[test]
+fn test_signed_literal_boundary() {
+    assert_eq!(
+        bounds("var a: array[-32768..32767] of byte;").unwrap(),
+        vec![(-32768, 32767)]
+    );
+}
+
+#[test]
+fn test_intermediate_overflow() {
+    let err = bounds("var a: array[0..32767+1-1] of byte;").unwrap_err();
+
+    assert_eq!(err.kind(), ErrorKind::Semantic);
+    assert_eq!(err.pos(), 16);
+    assert_eq!(err.tok_length(), 7);
+}
+
+#[test]
+fn test_final_overflow() {
+    let err = bounds("const n = 200; var a: array[0..n*n] of byte;").unwrap_err();
+
+    assert_eq!(err.pos(), 31);
+    assert_eq!(err.tok_length(), 3);
+}
+
+#[test]
+fn test_negated_overflow() {
+    let err = bounds("const n = -32768; var a: array[-n..0] of byte;").unwrap_err();
+
+    assert_eq!(err.pos(), 31);
+    assert_eq!(err.tok_length(), 2);
+}
+
+#[test]
+fn test_division_by_zero() {
+    let err = bounds("var a: array[0..10 div (2-2)] of byte;").unwrap_err();
+
+    assert_eq!(err.kind(), ErrorKind::Semantic);
+    assert_eq!(err.pos(), 23);
+    assert_eq!(err.tok_length(), 5);
+}
+
+#[test]
+fn test_unclosed_parenthesis() {
+    let err = bounds("var a: array[0..(1+2] of byte;").unwrap_err();
+
+    assert_eq!(err.pos(), 20);
+}
+
+#[test]
+fn test_missing_operand() {
+    let err = bounds("var a: array[0..1+] of byte;").unwrap_err();
+
+    assert_eq!(err.kind(), ErrorKind::Syntax);
+    assert_eq!(err.pos(), 18);
+}
@@ -1,3 +1,5 @@
+#![allow(clippy::bool_assert_comparison)]
+
 use analyzer::is_identifier;
 
 #[test]
@@ -64,3 +66,18 @@ fn test_double() {
 fn test_k() {
     assert_eq!(is_identifier("k"), true);
 }
+
+#[test]
+fn test_underscore() {
+    assert_eq!(is_identifier("a_b"), true);
+    assert_eq!(is_identifier("_tmp"), true);
+    assert_eq!(is_identifier("_"), true);
+    assert_eq!(is_identifier("1_a"), false);
+}
+
+#[test]
+fn test_non_ascii() {
+    assert_eq!(is_identifier("ä"), false);
+    assert_eq!(is_identifier("aä"), false);
+    assert_eq!(is_identifier("жжж"), false);
+}
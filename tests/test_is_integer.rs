@@ -1,3 +1,5 @@
+#![allow(clippy::bool_assert_comparison)]
+
 use analyzer::is_integer;
 
 #[test]
@@ -49,3 +51,9 @@ fn test_letters() {
 fn test_double() {
     assert_eq!(is_integer("-13.69"), false);
 }
+
+#[test]
+fn test_non_ascii_digits() {
+    assert_eq!(is_integer("١٢"), false);
+    assert_eq!(is_integer("1²"), false);
+}
@@ -0,0 +1,99 @@
+mod common;
+
+use analyzer::ast::TypeExpr;
+use analyzer::ErrorKind;
+use common::{run, type_of, types};
+
+#[test]
+fn test_array_of_array() {
+    let ty = &types("var m: array[1..3] of array[1..4] of byte;")[0];
+
+    match ty {
+        TypeExpr::Array { dims, elem, .. } => {
+            assert_eq!(dims.len(), 1);
+            assert!(matches!(**elem, TypeExpr::Array { .. }));
+        }
+        ty => panic!("expected array, found {:?}", ty),
+    }
+    assert_eq!(ty.to_string(), "array[1..3] of array[1..4] of byte");
+}
+
+#[test]
+fn test_deeply_nested_array() {
+    let ty = &types("var m: array[1..2] of array[char] of array['a'..'c', 0..1] of real;")[0];
+
+    assert_eq!(
+        ty.to_string(),
+        "array[1..2] of array[char] of array['a'..'c',0..1] of real"
+    );
+}
+
+#[test]
+fn test_packed_array() {
+    let ty = &types("var s: packed array[1..10] of char;")[0];
+
+    assert!(matches!(ty, TypeExpr::Array { packed: true, .. }));
+    assert_eq!(ty.to_string(), "packed array[1..10] of char");
+}
+
+#[test]
+fn test_packed_requires_array() {
+    let err = run("var s: packed byte;").unwrap_err();
+
+    assert_eq!(err.kind(), ErrorKind::Syntax);
+    assert_eq!(err.pos(), 14);
+}
+
+#[test]
+fn test_shorthand_is_distinct_but_equivalent() {
+    let types = types(
+        "type row = array[1..4] of byte; \
+         var a: array[1..3, 1..4] of byte; b: array[1..3] of array[1..4] of byte; c: array[1..3] of row;",
+    );
+
+    assert_ne!(types[0], types[1]);
+    assert!(types[0].is_equivalent(&types[1]));
+    assert!(types[1].is_equivalent(&types[2]));
+    assert_eq!(types[0].expanded().to_string(), types[1].to_string());
+}
+
+#[test]
+fn test_packing_is_not_equivalent() {
+    let types = types("var a: packed array[1..3] of byte; b: array[1..3] of byte;");
+
+    assert!(!types[0].is_equivalent(&types[1]));
+}
+
+#[test]
+fn test_different_bounds_are_not_equivalent() {
+    let types = types("var a: array[1..3, 1..4] of byte; b: array[1..4] of array[1..3] of byte;");
+
+    assert!(!types[0].is_equivalent(&types[1]));
+}
+
+#[test]
+fn test_nested_names_are_equivalent() {
+    let types = types(
+        "type TB = byte; \
+         var a: set of TB; b: set of byte; c: record x: TB end; d: record x: byte end; \
+         e: file of TB; f: file of byte; g: array[1..2] of record y: set of TB end; \
+         h: array[1..2] of record y: set of byte end;",
+    );
+
+    assert!(types[0].is_equivalent(&types[1]));
+    assert!(types[2].is_equivalent(&types[3]));
+    assert!(types[4].is_equivalent(&types[5]));
+    assert!(types[6].is_equivalent(&types[7]));
+    assert!(!types[0].is_equivalent(&types[5]));
+}
+
+#[test]
+fn test_different_enums_are_not_equivalent() {
+    let a = type_of("var a: (red, green);");
+    let b = type_of("var bb: (red, green);");
+    let c = types("type color = (red, green); var c: color; d: color;");
+
+    assert_eq!(a.to_string(), b.to_string());
+    assert!(!a.is_equivalent(&b));
+    assert!(c[0].is_equivalent(&c[1]));
+}
@@ -0,0 +1,175 @@
+mod common;
+
+use analyzer::ast::{SimpleType, TypeExpr};
+use analyzer::ErrorKind;
+use common::{run, type_of};
+
+#[test]
+fn test_enum_type() {
+    let ty = type_of("var c: (red, green, blue);");
+
+    match &ty {
+        TypeExpr::Enum(names) => {
+            let names: Vec<&str> = names.iter().map(|name| name.name.as_str()).collect();
+            assert_eq!(names, vec!["red", "green", "blue"]);
+        }
+        ty => panic!("expected enum, found {:?}", ty),
+    }
+    assert!(ty.is_ordinal());
+    assert_eq!(ty.to_string(), "(red, green, blue)");
+}
+
+#[test]
+fn test_enum_constant_clashes_with_variable() {
+    let err = run("var c: (red, green); green: byte;").unwrap_err();
+
+    assert_eq!(err.kind(), ErrorKind::Semantic);
+    assert_eq!(err.pos(), 21);
+}
+
+#[test]
+fn test_enum_constant_clashes_with_declared_name() {
+    let err = run("const red = 1; type color = (red, green);").unwrap_err();
+
+    assert_eq!(err.kind(), ErrorKind::Semantic);
+    assert_eq!(err.pos(), 29);
+}
+
+#[test]
+fn test_integer_subrange() {
+    let ty = type_of("var d: 1..31;");
+
+    match &ty {
+        TypeExpr::Subrange { range, host } => {
+            assert_eq!((range.low, range.high), (1, 31));
+            assert_eq!(**host, TypeExpr::Simple(SimpleType::Integer));
+        }
+        ty => panic!("expected subrange, found {:?}", ty),
+    }
+    assert!(ty.is_ordinal());
+    assert_eq!(ty.to_string(), "1..31");
+}
+
+#[test]
+fn test_constant_expression_subrange() {
+    let ty = type_of("const n = 10; var d: -n..n * 2;");
+
+    assert_eq!(ty.to_string(), "-10..20");
+}
+
+#[test]
+fn test_char_subrange() {
+    let ty = type_of("const z = 'z'; var ch: 'a'..z;");
+
+    match &ty {
+        TypeExpr::Subrange { range, host } => {
+            assert_eq!((range.low, range.high), ('a' as i32, 'z' as i32));
+            assert_eq!(**host, TypeExpr::Simple(SimpleType::Char));
+        }
+        ty => panic!("expected subrange, found {:?}", ty),
+    }
+    assert_eq!(ty.to_string(), "'a'..'z'");
+}
+
+#[test]
+fn test_enum_subrange() {
+    let ty = type_of("type color = (red, green, blue, black); var c: green..black;");
+
+    assert_eq!(ty.to_string(), "green..black");
+}
+
+#[test]
+fn test_subrange_bounds_order() {
+    let err = run("var d: 31..1;").unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Semantic);
+    assert_eq!(err.pos(), 11);
+
+    let err = run("var ch: 'z'..'a';").unwrap_err();
+    assert_eq!(err.pos(), 13);
+    assert_eq!(err.tok_length(), 3);
+
+    let err = run("type color = (red, green); var c: green..red;").unwrap_err();
+    assert_eq!(err.pos(), 41);
+}
+
+#[test]
+fn test_char_subrange_out_of_char_range() {
+    let err = run("var c: 'a'..'я';").unwrap_err();
+
+    assert_eq!(err.kind(), ErrorKind::Semantic);
+    assert_eq!(err.pos(), 12);
+    assert_eq!(err.tok_length(), 4);
+}
+
+#[test]
+fn test_subrange_bounds_of_different_types() {
+    let err = run("var d: 1..'z';").unwrap_err();
+
+    assert_eq!(err.kind(), ErrorKind::Semantic);
+    assert_eq!(err.pos(), 10);
+    assert_eq!(err.tok_length(), 3);
+}
+
+#[test]
+fn test_subrange_bound_not_ordinal() {
+    let err = run("var s: 'ab'..'z';").unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Semantic);
+    assert_eq!(err.pos(), 7);
+
+    let err = run("const pi = 3.14159; var d: 1..pi;").unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Semantic);
+    assert_eq!(err.pos(), 30);
+}
+
+#[test]
+fn test_enum_tag_type() {
+    assert!(run("type shape = (circle, square); \
+         var s: record case kind: shape of circle: (r: real); square: (side: real) end;")
+    .is_ok());
+}
+
+#[test]
+fn test_enum_constant_declaration() {
+    let part = run("type color = (red, green, blue); const c = green; var x: c..blue;").unwrap();
+
+    assert_eq!(
+        part.var_decls().next().unwrap().ty.to_string(),
+        "green..blue"
+    );
+}
+
+#[test]
+fn test_enum_constant_as_case_label() {
+    assert!(
+        run("type c = (r, g); var x: record case c of r: (a: byte); g: (b: byte) end;").is_ok()
+    );
+}
+
+#[test]
+fn test_signed_enum_constant() {
+    let err = run("type color = (red, green); const c = -green;").unwrap_err();
+
+    assert_eq!(err.kind(), ErrorKind::Semantic);
+    assert_eq!(err.pos(), 37);
+}
+
+#[test]
+fn test_boolean_constants() {
+    let ty = type_of("const yes = true; var a: array[false..yes] of byte;");
+
+    match ty.resolved() {
+        TypeExpr::Array { dims, .. } => {
+            assert_eq!(dims[0].bounds(), Some((0, 1)));
+            assert_eq!(dims[0].to_string(), "false..true");
+        }
+        ty => panic!("expected array, found {:?}", ty),
+    }
+}
+
+#[test]
+fn test_boolean_constant_is_taken() {
+    let err = run("var true: byte;").unwrap_err();
+
+    assert_eq!(err.kind(), ErrorKind::Semantic);
+    assert_eq!(err.pos(), 4);
+}
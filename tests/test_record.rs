@@ -0,0 +1,171 @@
+mod common;
+
+use analyzer::ast::TypeExpr;
+use analyzer::ErrorKind;
+use common::{run, type_of};
+
+#[test]
+fn test_simple_record() {
+    let ty = type_of("var p: record x, y: integer; name: char end;");
+
+    match &ty {
+        TypeExpr::Record(fields) => {
+            assert_eq!(fields.fixed.len(), 2);
+            assert_eq!(fields.fixed[0].names.len(), 2);
+            assert_eq!(fields.fixed[1].names[0].name, "name");
+            assert!(fields.variant.is_none());
+        }
+        ty => panic!("expected record, found {:?}", ty),
+    }
+    assert_eq!(ty.to_string(), "record x, y: integer; name: char; end");
+}
+
+#[test]
+fn test_empty_record() {
+    assert_eq!(type_of("var p: record end;").to_string(), "record end");
+}
+
+#[test]
+fn test_nested_record() {
+    let ty = type_of("var p: record a: record a: byte; end; b: real; end;");
+
+    assert_eq!(
+        ty.to_string(),
+        "record a: record a: byte; end; b: real; end"
+    );
+}
+
+#[test]
+fn test_array_of_records() {
+    let ty = type_of("type pt = record x, y: real end; var ps: array[1..10] of pt;");
+
+    match ty.resolved() {
+        TypeExpr::Array { elem, .. } => {
+            assert!(matches!(elem.resolved(), TypeExpr::Record(_)));
+        }
+        ty => panic!("expected array, found {:?}", ty),
+    }
+    assert_eq!(ty.to_string(), "array[1..10] of pt");
+}
+
+#[test]
+fn test_field_names_do_not_clash_with_variables() {
+    assert!(run("var x: byte; p: record x: byte end;").is_ok());
+}
+
+#[test]
+fn test_duplicate_field() {
+    let err = run("var p: record x: byte; X: word end;").unwrap_err();
+
+    assert_eq!(err.kind(), ErrorKind::Semantic);
+    assert_eq!(err.pos(), 23);
+    assert_eq!(err.tok_length(), 1);
+}
+
+#[test]
+fn test_variant_part() {
+    let ty = type_of(
+        "var s: record kind: byte; case tag: byte of 0: (r: real); 1, 2: (c: char; n: integer) end;",
+    );
+
+    match &ty {
+        TypeExpr::Record(fields) => {
+            let variant = fields.variant.as_ref().unwrap();
+            assert_eq!(variant.tag.as_ref().unwrap().name, "tag");
+            assert_eq!(variant.variants.len(), 2);
+            assert_eq!(variant.variants[1].labels, vec![1, 2]);
+            assert_eq!(variant.variants[1].fields.fixed.len(), 2);
+        }
+        ty => panic!("expected record, found {:?}", ty),
+    }
+    assert_eq!(
+        ty.to_string(),
+        "record kind: byte; case tag: byte of 0: (r: real); 1, 2: (c: char; n: integer); end"
+    );
+}
+
+#[test]
+fn test_variant_without_tag_name() {
+    assert!(run("var s: record case byte of 0: (a: byte); 1: (b: word); end;").is_ok());
+}
+
+#[test]
+fn test_variant_field_clashes_with_fixed_field() {
+    let err = run("var s: record a: byte; case byte of 0: (a: word) end;").unwrap_err();
+
+    assert_eq!(err.kind(), ErrorKind::Semantic);
+    assert_eq!(err.pos(), 40);
+}
+
+#[test]
+fn test_duplicate_case_label() {
+    let err = run("var s: record case byte of 0: (a: byte); 0: (b: word) end;").unwrap_err();
+
+    assert_eq!(err.kind(), ErrorKind::Semantic);
+    assert_eq!(err.pos(), 41);
+}
+
+#[test]
+fn test_non_ordinal_tag_type() {
+    let err = run("var s: record case t: real of 0: (a: byte) end;").unwrap_err();
+
+    assert_eq!(err.kind(), ErrorKind::Semantic);
+    assert_eq!(err.pos(), 22);
+}
+
+#[test]
+fn test_missing_end() {
+    let err = run("var p: record x: byte;").unwrap_err();
+
+    assert_eq!(err.kind(), ErrorKind::Syntax);
+}
+
+#[test]
+fn test_char_case_labels() {
+    let ty = type_of("var s: record case c: char of 'a', 'b': (x: byte); 'z': (y: word) end;");
+
+    assert_eq!(
+        ty.to_string(),
+        "record case c: char of 'a', 'b': (x: byte); 'z': (y: word); end"
+    );
+}
+
+#[test]
+fn test_boolean_case_labels() {
+    assert!(run("var s: record case b: boolean of true: (x: byte); false: (y: word) end;").is_ok());
+
+    let err = run("var s: record case b: boolean of 5: (x: byte) end;").unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Semantic);
+    assert_eq!(err.pos(), 33);
+}
+
+#[test]
+fn test_case_label_out_of_range() {
+    let err = run("var s: record case byte of 300: (x: byte) end;").unwrap_err();
+
+    assert_eq!(err.kind(), ErrorKind::Semantic);
+    assert_eq!(err.pos(), 27);
+    assert_eq!(err.tok_length(), 3);
+}
+
+#[test]
+fn test_case_label_of_wrong_type() {
+    let err = run("var s: record case c: char of 1: (x: byte) end;").unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Semantic);
+    assert_eq!(err.pos(), 30);
+
+    let err = run("var s: record case byte of 'a': (x: byte) end;").unwrap_err();
+    assert_eq!(err.pos(), 27);
+
+    let err = run("type t = (p, q); var s: record case t of p: (x: byte); true: (y: byte) end;")
+        .unwrap_err();
+    assert_eq!(err.pos(), 55);
+}
+
+#[test]
+fn test_subrange_tag_type() {
+    assert!(run("var s: record case d: 1..3 of 1: (x: byte); 3: (y: word) end;").is_ok());
+
+    let err = run("var s: record case d: 1..3 of 4: (x: byte) end;").unwrap_err();
+    assert_eq!(err.pos(), 30);
+}
@@ -0,0 +1,119 @@
+mod common;
+
+use analyzer::ast::TypeExpr;
+use analyzer::ErrorKind;
+use common::{run, type_strings};
+
+#[test]
+fn test_set_types() {
+    assert_eq!(
+        type_strings(
+            "type color = (red, green); var a: set of char; b: set of 0..255; c: set of color;"
+        ),
+        vec!["set of char", "set of 0..255", "set of color"]
+    );
+}
+
+#[test]
+fn test_set_too_large() {
+    let err = run("var s: set of 0..256;").unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Semantic);
+    assert_eq!(err.pos(), 14);
+    assert_eq!(err.tok_length(), 6);
+
+    let err = run("var s: set of integer;").unwrap_err();
+    assert_eq!(err.pos(), 14);
+
+    let err = run("var s: set of -1..10;").unwrap_err();
+    assert_eq!(err.pos(), 14);
+}
+
+#[test]
+fn test_set_ordinal_range() {
+    assert!(run("var s: set of byte;").is_ok());
+
+    let err = run("var s: set of shortint;").unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Semantic);
+    assert!(err.to_string().contains("negative ordinals"));
+
+    let err = run("var s: set of 1..256;").unwrap_err();
+    assert!(err.to_string().contains("can't exceed 255"));
+}
+
+#[test]
+fn test_set_of_non_ordinal() {
+    let err = run("var s: set of real;").unwrap_err();
+
+    assert_eq!(err.kind(), ErrorKind::Semantic);
+    assert_eq!(err.pos(), 14);
+}
+
+#[test]
+fn test_file_types() {
+    assert_eq!(
+        type_strings(
+            "type rec = record a: byte end; \
+             var a: file of integer; b: text; c: file of rec; d: file;"
+        ),
+        vec!["file of integer", "text", "file of rec", "file"]
+    );
+}
+
+#[test]
+fn test_file_of_file() {
+    let err = run("var f: file of text;").unwrap_err();
+
+    assert_eq!(err.kind(), ErrorKind::Semantic);
+    assert_eq!(err.pos(), 15);
+}
+
+#[test]
+fn test_pointer_to_builtin() {
+    assert_eq!(type_strings("var p: ^integer;"), vec!["^integer"]);
+}
+
+#[test]
+fn test_pointer_forward_reference() {
+    let part = run("type PNode = ^TNode; \
+         TNode = record value: integer; next: PNode end; \
+         var head: PNode;")
+    .unwrap();
+    let decl = part.var_decls().next().unwrap();
+
+    assert_eq!(decl.ty.describe(), "PNode (^TNode)");
+    match decl.ty.resolved() {
+        TypeExpr::Pointer(target) => assert_eq!(target.name, "TNode"),
+        ty => panic!("expected pointer, found {:?}", ty),
+    }
+}
+
+#[test]
+fn test_self_referencing_record() {
+    assert!(run("type TNode = record next: ^TNode end;").is_ok());
+}
+
+#[test]
+fn test_forward_reference_never_declared() {
+    let err = run("type PNode = ^TNode; TItem = byte;").unwrap_err();
+
+    assert_eq!(err.kind(), ErrorKind::Semantic);
+    assert_eq!(err.pos(), 14);
+    assert_eq!(err.tok_length(), 5);
+}
+
+#[test]
+fn test_forward_reference_across_sections() {
+    let err = run("type PNode = ^TNode; type TNode = byte;").unwrap_err();
+
+    assert_eq!(err.pos(), 14);
+}
+
+#[test]
+fn test_pointer_outside_type_section() {
+    let err = run("var p: ^TNode;").unwrap_err();
+    assert_eq!(err.pos(), 8);
+
+    let err = run("var x: byte; p: ^x;").unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Semantic);
+    assert_eq!(err.pos(), 17);
+}
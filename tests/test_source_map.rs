@@ -0,0 +1,124 @@
+use analyzer::{analyze, tokenize, ErrorKind, Location, SourceMap};
+
+fn location(line: usize, column: usize) -> Location {
+    Location { line, column }
+}
+
+#[test]
+fn test_locations() {
+    let map = SourceMap::new("var\n  a: byte;\n");
+
+    assert_eq!(map.location(0), location(1, 1));
+    assert_eq!(map.location(3), location(1, 4));
+    assert_eq!(map.location(4), location(2, 1));
+    assert_eq!(map.location(6), location(2, 3));
+    assert_eq!(map.location(14), location(2, 11));
+    assert_eq!(map.location(15), location(3, 1));
+}
+
+#[test]
+fn test_crlf_and_lone_cr() {
+    let map = SourceMap::new("var\r\na: byte;\rb: word;");
+
+    assert_eq!(map.line_count(), 3);
+    assert_eq!(map.location(3), location(1, 4));
+    assert_eq!(map.location(4), location(1, 5));
+    assert_eq!(map.location(5), location(2, 1));
+    assert_eq!(map.location(14), location(3, 1));
+}
+
+#[test]
+fn test_line_text() {
+    let map = SourceMap::new("var\r\n  a: byte;\n\nb: word;");
+
+    assert_eq!(map.line_text(1), Some("var"));
+    assert_eq!(map.line_text(2), Some("  a: byte;"));
+    assert_eq!(map.line_text(3), Some(""));
+    assert_eq!(map.line_text(4), Some("b: word;"));
+    assert_eq!(map.line_text(0), None);
+    assert_eq!(map.line_text(5), None);
+}
+
+#[test]
+fn test_token_locations() {
+    let tokens = tokenize(String::from("var\r\n\ta, b: byte;")).unwrap();
+
+    assert_eq!(tokens[0].location(), location(1, 1));
+    assert_eq!(tokens[1].location(), location(2, 2));
+    assert_eq!(tokens[3].location(), location(2, 5));
+}
+
+#[test]
+fn test_lexical_error_location() {
+    let err = tokenize(String::from("var\n  a: @;")).unwrap_err();
+
+    assert_eq!(err.location(), location(2, 6));
+    assert_eq!(
+        err.to_string(),
+        "Error at 2:6: lexical error: illegal character `@`"
+    );
+}
+
+#[test]
+fn test_parser_error_location() {
+    let err =
+        analyze(tokenize(String::from("var\r\n  a: byte;\r\n  a: word;")).unwrap()).unwrap_err();
+
+    assert_eq!(err.location(), location(3, 3));
+    assert!(err.to_string().starts_with("Error at 3:3: semantic error:"));
+}
+
+#[test]
+fn test_end_of_input_location() {
+    let err = analyze(tokenize(String::from("var\n  a: byte")).unwrap()).unwrap_err();
+
+    assert_eq!(err.location(), location(2, 10));
+}
+
+#[test]
+fn test_multibyte_positions() {
+    let source = "var имя: byte; x: @;";
+    let err = tokenize(String::from(source)).unwrap_err();
+
+    assert_eq!(err.pos(), source.find('@').unwrap());
+    assert_eq!(err.location(), location(1, 19));
+    assert_eq!(err.span().slice(source), "@");
+}
+
+#[test]
+fn test_multibyte_token_spans() {
+    let source = "const s = 'привет'; var ä: byte;";
+    let tokens = tokenize(String::from(source)).unwrap();
+
+    for tok in &tokens {
+        assert_eq!(tok.span().slice(source), tok.text());
+    }
+    assert_eq!(tokens[3].string_value().unwrap(), "привет");
+    assert_eq!(tokens[6].location(), location(1, 25));
+}
+
+#[test]
+fn test_multibyte_parser_error() {
+    let source = "const s = 'жжж'; var ä: byte;";
+    let err = analyze(tokenize(String::from(source)).unwrap()).unwrap_err();
+
+    assert_eq!(err.kind(), ErrorKind::Syntax);
+    assert_eq!(err.span().slice(source), "ä");
+    assert_eq!(err.location(), location(1, 22));
+    assert!(err.to_string().contains("ASCII letters"));
+
+    let source = "const s = 'жжж';\nvar s: byte;";
+    let err = analyze(tokenize(String::from(source)).unwrap()).unwrap_err();
+
+    assert_eq!(err.span().slice(source), "s");
+    assert_eq!(err.location(), location(2, 5));
+}
+
+#[test]
+fn test_illegal_multibyte_character() {
+    let source = "var a: byte;€";
+    let err = tokenize(String::from(source)).unwrap_err();
+
+    assert_eq!(err.tok_length(), '€'.len_utf8());
+    assert_eq!(err.span().split(source), ("var a: byte;", "€", ""));
+}
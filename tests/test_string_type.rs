@@ -0,0 +1,67 @@
+mod common;
+
+use analyzer::ast::TypeExpr;
+use analyzer::ErrorKind;
+use common::{run, type_of};
+
+#[test]
+fn test_plain_string() {
+    let ty = type_of("var s: string;");
+
+    assert_eq!(ty, TypeExpr::String(None));
+    assert_eq!(ty.to_string(), "string");
+    assert_eq!(ty.string_length(), Some(255));
+}
+
+#[test]
+fn test_sized_string() {
+    let ty = type_of("var s: String[80];");
+
+    assert_eq!(ty, TypeExpr::String(Some(80)));
+    assert_eq!(ty.to_string(), "string[80]");
+    assert_eq!(ty.string_length(), Some(80));
+}
+
+#[test]
+fn test_string_length_expression() {
+    let ty = type_of("const n = 10; type name = string[n * 2]; var s: name;");
+
+    assert_eq!(ty.string_length(), Some(20));
+    assert_eq!(ty.describe(), "name (string[20])");
+}
+
+#[test]
+fn test_string_bounds() {
+    assert_eq!(type_of("var s: string[1];").string_length(), Some(1));
+    assert_eq!(type_of("var s: string[255];").string_length(), Some(255));
+    assert_eq!(type_of("var s: byte;").string_length(), None);
+}
+
+#[test]
+fn test_string_length_out_of_range() {
+    let err = run("var s: string[256];").unwrap_err();
+
+    assert_eq!(err.kind(), ErrorKind::Semantic);
+    assert_eq!(err.pos(), 14);
+    assert_eq!(err.tok_length(), 3);
+
+    let err = run("var s: string[0];").unwrap_err();
+
+    assert_eq!(err.kind(), ErrorKind::Semantic);
+    assert_eq!(err.pos(), 14);
+}
+
+#[test]
+fn test_array_of_strings() {
+    let ty = type_of("var names: array[1..10] of string[20];");
+
+    assert_eq!(ty.to_string(), "array[1..10] of string[20]");
+}
+
+#[test]
+fn test_unclosed_string_length() {
+    let err = run("var s: string[20;").unwrap_err();
+
+    assert_eq!(err.kind(), ErrorKind::Syntax);
+    assert_eq!(err.pos(), 16);
+}
@@ -0,0 +1,243 @@
+use analyzer::{tokenize, ErrorKind, Keyword, Punct, Span, TokenKind};
+
+fn kinds(source: &str) -> Vec<TokenKind> {
+    tokenize(String::from(source))
+        .unwrap()
+        .iter()
+        .map(|tok| tok.kind())
+        .collect()
+}
+
+#[test]
+fn test_empty() {
+    assert!(kinds("").is_empty());
+}
+
+#[test]
+fn test_keywords() {
+    assert_eq!(
+        kinds("VAR array Of byte"),
+        vec![
+            TokenKind::Keyword(Keyword::Var),
+            TokenKind::Keyword(Keyword::Array),
+            TokenKind::Keyword(Keyword::Of),
+            TokenKind::Keyword(Keyword::Byte),
+        ]
+    );
+}
+
+#[test]
+fn test_declaration() {
+    assert_eq!(
+        kinds("var a1,b:array[2:10] of word;"),
+        vec![
+            TokenKind::Keyword(Keyword::Var),
+            TokenKind::Identifier,
+            TokenKind::Punct(Punct::Comma),
+            TokenKind::Identifier,
+            TokenKind::Punct(Punct::Colon),
+            TokenKind::Keyword(Keyword::Array),
+            TokenKind::Punct(Punct::LeftBracket),
+            TokenKind::IntegerLiteral(2),
+            TokenKind::Punct(Punct::Colon),
+            TokenKind::IntegerLiteral(10),
+            TokenKind::Punct(Punct::RightBracket),
+            TokenKind::Keyword(Keyword::Of),
+            TokenKind::Keyword(Keyword::Word),
+            TokenKind::Punct(Punct::Semicolon),
+        ]
+    );
+}
+
+#[test]
+fn test_text_and_span() {
+    let tokens = tokenize(String::from("var  Accum;")).unwrap();
+
+    assert_eq!(tokens[1].text(), "Accum");
+    assert_eq!(tokens[1].span(), Span::new(5, 10));
+    assert_eq!(tokens[2].span(), Span::new(10, 11));
+}
+
+#[test]
+fn test_malformed_word() {
+    assert_eq!(kinds("2x"), vec![TokenKind::Unknown]);
+}
+
+#[test]
+fn test_underscore_identifiers() {
+    let tokens = tokenize(String::from("var a_b, _tmp, x_1_: byte;")).unwrap();
+    let idents: Vec<&str> = tokens
+        .iter()
+        .filter(|tok| tok.kind() == TokenKind::Identifier)
+        .map(|tok| tok.text())
+        .collect();
+
+    assert_eq!(idents, vec!["a_b", "_tmp", "x_1_"]);
+    assert_eq!(kinds("2_a"), vec![TokenKind::Unknown]);
+}
+
+#[test]
+fn test_non_ascii_word() {
+    assert_eq!(kinds("äb"), vec![TokenKind::Unknown]);
+    assert_eq!(kinds("aé"), vec![TokenKind::Unknown]);
+}
+
+#[test]
+fn test_integer_overflow() {
+    assert!(tokenize(String::from("99999999999")).is_err());
+}
+
+#[test]
+fn test_illegal_character() {
+    for source in [
+        "var a@: byte;",
+        "var a#: byte;",
+        "var a%: byte;",
+        "var a?: byte;",
+    ] {
+        let err = tokenize(String::from(source)).unwrap_err();
+
+        assert_eq!(err.kind(), ErrorKind::Lexical);
+        assert_eq!(err.pos(), 5);
+        assert_eq!(err.tok_length(), 1);
+    }
+}
+
+#[test]
+fn test_illegal_character_message() {
+    let err = tokenize(String::from("var @")).unwrap_err();
+
+    assert_eq!(
+        format!("{}", err),
+        "Error at 1:5: lexical error: illegal character `@`"
+    );
+}
+
+#[test]
+fn test_multi_char_punct() {
+    assert_eq!(
+        kinds(".. := <= >= <> ^"),
+        vec![
+            TokenKind::Punct(Punct::DotDot),
+            TokenKind::Punct(Punct::Assign),
+            TokenKind::Punct(Punct::LessEqual),
+            TokenKind::Punct(Punct::GreaterEqual),
+            TokenKind::Punct(Punct::NotEqual),
+            TokenKind::Punct(Punct::Caret),
+        ]
+    );
+}
+
+#[test]
+fn test_maximal_munch() {
+    let tokens = tokenize(String::from("[1..10]")).unwrap();
+
+    assert_eq!(tokens[2].kind(), TokenKind::Punct(Punct::DotDot));
+    assert_eq!(tokens[2].text(), "..");
+    assert_eq!(tokens[2].span(), Span::new(2, 4));
+    assert_eq!(tokens[3].kind(), TokenKind::IntegerLiteral(10));
+}
+
+#[test]
+fn test_split_punct() {
+    assert_eq!(
+        kinds(". . : ="),
+        vec![
+            TokenKind::Punct(Punct::Dot),
+            TokenKind::Punct(Punct::Dot),
+            TokenKind::Punct(Punct::Colon),
+            TokenKind::Punct(Punct::Equal),
+        ]
+    );
+}
+
+#[test]
+fn test_real_literals() {
+    assert_eq!(
+        kinds("2.75 2e5 1.5E-3"),
+        vec![
+            TokenKind::RealLiteral(2.75),
+            TokenKind::RealLiteral(2e5),
+            TokenKind::RealLiteral(1.5e-3),
+        ]
+    );
+}
+
+#[test]
+fn test_range_is_not_real() {
+    assert_eq!(
+        kinds("1..10"),
+        vec![
+            TokenKind::IntegerLiteral(1),
+            TokenKind::Punct(Punct::DotDot),
+            TokenKind::IntegerLiteral(10),
+        ]
+    );
+}
+
+#[test]
+fn test_string_literal() {
+    let tokens = tokenize(String::from("s = 'it''s';")).unwrap();
+
+    assert_eq!(tokens[2].kind(), TokenKind::StringLiteral);
+    assert_eq!(tokens[2].text(), "'it''s'");
+    assert_eq!(tokens[2].string_value().unwrap(), "it's");
+    assert_eq!(tokens[2].span(), Span::new(4, 11));
+    assert_eq!(tokens[3].kind(), TokenKind::Punct(Punct::Semicolon));
+}
+
+#[test]
+fn test_unterminated_string() {
+    let err = tokenize(String::from("s = 'abc")).unwrap_err();
+
+    assert_eq!(err.kind(), ErrorKind::Lexical);
+    assert_eq!(err.pos(), 4);
+    assert_eq!(err.tok_length(), 4);
+}
+
+#[test]
+fn test_whitespace() {
+    let tokens = tokenize(String::from("var\ta,\nb:\x0cbyte;\r\n")).unwrap();
+    let texts: Vec<&str> = tokens.iter().map(|tok| tok.text()).collect();
+
+    assert_eq!(texts, vec!["var", "a", ",", "b", ":", "byte", ";"]);
+    assert_eq!(tokens[1].span(), Span::new(4, 5));
+    assert_eq!(tokens[3].span(), Span::new(7, 8));
+}
+
+#[test]
+fn test_crlf_positions() {
+    let tokens = tokenize(String::from("var\r\n  a: byte;\r\n  b: word;")).unwrap();
+
+    assert_eq!(tokens[1].span(), Span::new(7, 8));
+    assert_eq!(tokens[5].span(), Span::new(19, 20));
+}
+
+#[test]
+fn test_string_ends_at_line_break() {
+    let err = tokenize(String::from("s = 'abc\r\n';")).unwrap_err();
+
+    assert_eq!(err.kind(), ErrorKind::Lexical);
+    assert_eq!(err.pos(), 4);
+    assert_eq!(err.tok_length(), 4);
+}
+
+#[test]
+fn test_split_never_panics() {
+    let source = "var ж: byte;";
+
+    assert_eq!(Span::new(4, 6).split(source), ("var ", "ж", ": byte;"));
+    // Offsets inside a char widen the span to the whole char.
+    assert_eq!(Span::new(5, 5).split(source), ("var ", "ж", ": byte;"));
+    assert_eq!(Span::new(5, 6).split(source), ("var ", "ж", ": byte;"));
+    // Offsets past the end are clamped.
+    assert_eq!(Span::new(14, 15).split(source), ("var ж: byte;", "", ""));
+    assert_eq!(Span::new(6, 2).split(source), ("var ж", "", ": byte;"));
+
+    for start in 0..=source.len() + 1 {
+        for end in 0..=source.len() + 1 {
+            let (before, span, after) = Span::new(start, end).split(source);
+            assert_eq!(format!("{}{}{}", before, span, after), source);
+        }
+    }
+}
@@ -0,0 +1,79 @@
+mod common;
+
+use analyzer::ast::{SimpleType, TypeExpr};
+use analyzer::ErrorKind;
+use common::run;
+
+#[test]
+fn test_named_type() {
+    let part = run("type TVec = array[1..3] of real; var v: TVec;").unwrap();
+    let decl = part.var_decls().next().unwrap();
+
+    match &decl.ty {
+        TypeExpr::Named { name, ty } => {
+            assert_eq!(name.name, "TVec");
+            assert_eq!(ty.to_string(), "array[1..3] of real");
+        }
+        ty => panic!("expected named type, found {:?}", ty),
+    }
+    assert_eq!(decl.ty.describe(), "TVec (array[1..3] of real)");
+}
+
+#[test]
+fn test_alias_chain() {
+    let part = run("type a = byte; b = A; var x: b;").unwrap();
+    let decl = part.var_decls().next().unwrap();
+
+    assert_eq!(decl.ty.to_string(), "b");
+    assert_eq!(*decl.ty.resolved(), TypeExpr::Simple(SimpleType::Byte));
+}
+
+#[test]
+fn test_report_includes_structure() {
+    let part = run("type TVec = array[1..3] of real; var v: TVec; n: byte;").unwrap();
+
+    assert_eq!(
+        part.report(),
+        "Identifier: v, type: TVec (array[1..3] of real)\n\
+         Identifier: n, type: byte\n"
+    );
+}
+
+#[test]
+fn test_unknown_type() {
+    let err = run("var v: TVec;").unwrap_err();
+
+    assert_eq!(err.kind(), ErrorKind::Semantic);
+    assert_eq!(err.pos(), 7);
+    assert_eq!(err.tok_length(), 4);
+}
+
+#[test]
+fn test_type_declared_later() {
+    let err = run("var v: TVec; type TVec = byte;").unwrap_err();
+
+    assert_eq!(err.pos(), 7);
+}
+
+#[test]
+fn test_self_referential_type() {
+    let err = run("type t = t;").unwrap_err();
+
+    assert_eq!(err.kind(), ErrorKind::Semantic);
+    assert_eq!(err.pos(), 9);
+}
+
+#[test]
+fn test_variable_is_not_a_type() {
+    let err = run("var a: byte; b: a;").unwrap_err();
+
+    assert_eq!(err.kind(), ErrorKind::Semantic);
+    assert_eq!(err.pos(), 16);
+}
+
+#[test]
+fn test_type_name_clash() {
+    let err = run("type t = byte; var t: word;").unwrap_err();
+
+    assert_eq!(err.pos(), 19);
+}